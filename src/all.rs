@@ -0,0 +1,116 @@
+use crate::{
+    error::Error,
+    expectations::{Expectations, Verdict},
+    input::Source,
+    progress::Progress,
+    solutions::{
+        self,
+        solver::{Registry, Solver},
+        Failure, Invocation, Report, Summary,
+    },
+};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+pub enum Event<'a> {
+    Solved { solver: &'a dyn Solver, report: &'a Report, verdict: &'a Verdict<'a> },
+    Failed { solver: &'a dyn Solver, error: &'a Error },
+}
+
+pub fn load(inputs: &Path, expect: Option<&Path>) -> Result<(BTreeMap<u8, PathBuf>, Expectations), Failure> {
+    let failure = |path: &Path| {
+        let input = Source::Path(path.to_path_buf());
+        move |error| Failure { day: None, input, error }
+    };
+    let discovered = solutions::discover_inputs(inputs).map_err(failure(inputs))?;
+    let expectations = match expect {
+        Some(path) => Expectations::load(path).map_err(failure(path))?,
+        None => Expectations::default(),
+    };
+    Ok((discovered, expectations))
+}
+
+pub fn run(
+    registry: &Registry,
+    inputs: &BTreeMap<u8, PathBuf>,
+    expectations: &Expectations,
+    progress: &Progress,
+    mut on_event: impl FnMut(Event),
+) -> Summary {
+    let mut summary = Summary::default();
+    for solver in registry.iter() {
+        if progress.is_cancelled() {
+            break;
+        }
+        let input = match inputs.get(&solver.day()) {
+            Some(input) => input.clone(),
+            None => {
+                summary.missing.push(solver.day());
+                continue;
+            }
+        };
+        let invocation = Invocation::new(solver.day(), input, solver.parts());
+        let result = invocation.run_checked(registry, progress, expectations, |report, verdict| {
+            if let Verdict::Fail { .. } = verdict {
+                summary.mismatches += 1;
+            }
+            on_event(Event::Solved { solver, report: &report, verdict });
+            summary.reports.push(report);
+        });
+        if let Err(error) = result {
+            on_event(Event::Failed { solver, error: &error });
+            summary.failures.push(Failure {
+                day: Some(invocation.day),
+                input: invocation.input,
+                error,
+            });
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Result, solutions};
+
+    #[test]
+    fn it_solves_every_day_with_an_input_and_checks_the_answers() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("advent_2020-all-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join("day13.txt"), "939\n7,13,x,x,59,x,31,19\n")?;
+        std::fs::write(dir.join("day14.txt"), "mask = XX10\n")?;
+        let inputs = solutions::discover_inputs(&dir)?;
+        let expectations = Expectations::parse("[day13]\npart1 = 295\npart2 = 1\n")?;
+        let mut events = Vec::new();
+        let summary = run(&solutions::registry(), &inputs, &expectations, &Progress::new(), |event| {
+            events.push(match event {
+                Event::Solved { solver, verdict, .. } => (solver.day(), *verdict == Verdict::Pass),
+                Event::Failed { solver, .. } => (solver.day(), false),
+            })
+        });
+        std::fs::remove_dir_all(&dir)?;
+        assert_eq!(events, [(13, true), (13, false), (14, false)]);
+        assert_eq!(summary.reports.len(), 2);
+        assert_eq!(summary.mismatches, 1);
+        assert_eq!(summary.failures.iter().map(|failure| failure.day).collect::<Vec<_>>(), [Some(14)]);
+        assert!(!summary.missing.contains(&13) && summary.missing.contains(&11));
+        Ok(assert_eq!(summary.exit_code(), 4))
+    }
+
+    #[test]
+    fn it_reports_which_file_failed_to_load() {
+        let missing = Path::new("does-not-exist");
+        let failure = load(Path::new("."), Some(missing)).unwrap_err();
+        assert_eq!((failure.day, failure.input, failure.error.kind()), (None, Source::Path(missing.to_path_buf()), "io"))
+    }
+
+    #[test]
+    fn mismatched_answers_fail_the_run() {
+        let summary = Summary { mismatches: 2, ..Summary::default() };
+        assert_eq!(summary.exit_code(), 1);
+        assert_eq!(Summary::default().exit_code(), 0)
+    }
+}
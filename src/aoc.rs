@@ -0,0 +1,405 @@
+use crate::{
+    error::{Error, Result},
+    input::Source,
+    solutions::{
+        solver::{Part, Registry, Value},
+        Failure, Invocation,
+    },
+};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+pub const YEAR: u16 = 2020;
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = concat!("advent_2020/", env!("CARGO_PKG_VERSION"));
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Submission {
+    Correct,
+    Incorrect { hint: Option<String>, wait: Option<Duration> },
+    TooSoon { wait: Duration },
+    AlreadySolved,
+    Unrecognized(String),
+}
+
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Client {
+    pub fn new(session: impl Into<String>) -> Client {
+        Client {
+            base_url: BASE_URL.to_string(),
+            session: session.into(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(USER_AGENT)
+                .build(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Client {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn input(&self, day: u8) -> Result<String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, YEAR, day);
+        let response = self.agent.get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|err| request_error(day, err))?;
+        Ok(response.into_string()?)
+    }
+
+    pub fn submit(&self, day: u8, part: Part, answer: &Value) -> Result<Submission> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, YEAR, day);
+        let response = self.agent.post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.number().to_string()), ("answer", &answer.to_string())])
+            .map_err(|err| request_error(day, err))?;
+        Ok(Submission::parse(&response.into_string()?))
+    }
+
+    pub fn submit_with_retry(
+        &self,
+        day: u8,
+        part: Part,
+        answer: &Value,
+        mut on_wait: impl FnMut(Duration),
+    ) -> Result<Submission> {
+        loop {
+            match self.submit(day, part, answer)? {
+                Submission::TooSoon { wait } => {
+                    on_wait(wait);
+                    std::thread::sleep(wait + Duration::from_secs(1));
+                }
+                submission => return Ok(submission),
+            }
+        }
+    }
+}
+
+fn request_error(day: u8, err: ureq::Error) -> Error {
+    match err {
+        ureq::Error::Status(400, _) | ureq::Error::Status(401, _) => {
+            Error::http("the session token was rejected; copy a fresh one from the browser's cookies")
+        }
+        ureq::Error::Status(404, _) => Error::http(format!("day {} of {} is not unlocked yet", day, YEAR)),
+        ureq::Error::Status(status, _) => Error::http(format!("the server responded with status {}", status)),
+        ureq::Error::Transport(err) => Error::http(err),
+    }
+}
+
+impl Submission {
+    pub fn parse(page: &str) -> Submission {
+        let article = page.split("<article>")
+            .nth(1)
+            .and_then(|article| article.split("</article>").next())
+            .unwrap_or(page);
+        let text = strip_tags(article);
+        if text.contains("That's the right answer") {
+            Submission::Correct
+        } else if text.contains("That's not the right answer") {
+            let hint = ["too high", "too low"].iter()
+                .find(|hint| text.contains(*hint))
+                .map(|hint| format!("your answer is {}", hint));
+            Submission::Incorrect {
+                hint,
+                wait: parse_wait(&text),
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Submission::TooSoon {
+                wait: parse_wait(&text).unwrap_or_else(|| Duration::from_secs(60)),
+            }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Submission::AlreadySolved
+        } else {
+            Submission::Unrecognized(text)
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Submission::Correct | Submission::AlreadySolved => 0,
+            Submission::Incorrect { .. } => 1,
+            Submission::TooSoon { .. } | Submission::Unrecognized(_) => 7,
+        }
+    }
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => (),
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn parse_wait(text: &str) -> Option<Duration> {
+    if text.contains("wait one minute") {
+        return Some(Duration::from_secs(60));
+    }
+    let remaining = text.split("You have ").nth(1)?.split(" left to wait").next()?;
+    remaining.split_whitespace()
+        .map(|amount| {
+            let (number, unit) = amount.split_at(amount.len().checked_sub(1)?);
+            let number = number.parse::<u64>().ok()?;
+            match unit {
+                "h" => Some(number * 3600),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Cache {
+        Cache { dir: dir.into() }
+    }
+
+    pub fn default_dir() -> PathBuf {
+        if let Some(dir) = std::env::var_os("AOC_CACHE_DIR") {
+            PathBuf::from(dir)
+        } else if let Some(dir) = std::env::var_os("XDG_CACHE_HOME") {
+            Path::new(&dir).join("advent_2020")
+        } else if let Some(home) = std::env::var_os("HOME") {
+            Path::new(&home).join(".cache").join("advent_2020")
+        } else {
+            PathBuf::from(".advent_2020")
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{}.txt", day))
+    }
+
+    pub fn fetch(&self, client: &Client, day: u8, force: bool) -> Result<PathBuf> {
+        let path = self.path(day);
+        if force || !path.exists() {
+            let input = client.input(day)?;
+            std::fs::create_dir_all(&self.dir)?;
+            std::fs::write(&path, input)?;
+        }
+        Ok(path)
+    }
+
+    pub fn fetch_all(
+        &self,
+        client: &Client,
+        days: &[u8],
+        force: bool,
+        mut on_fetched: impl FnMut(u8, &Path),
+    ) -> std::result::Result<(), Failure> {
+        for &day in days {
+            let path = self.fetch(client, day, force).map_err(|error| Failure {
+                day: Some(day),
+                input: Source::Path(self.path(day)),
+                error,
+            })?;
+            on_fetched(day, &path);
+        }
+        Ok(())
+    }
+
+    pub fn answer(
+        &self,
+        registry: &Registry,
+        day: u8,
+        part: Part,
+        given: Option<String>,
+    ) -> std::result::Result<Value, Failure> {
+        if let Some(answer) = given {
+            return Ok(answer.parse::<i128>().map_or(Value::Text(answer), Value::Integer));
+        }
+        let invocation = Invocation::new(day, Source::Cached { day, path: self.path(day) }, &[part]);
+        match invocation.run(registry) {
+            Ok(mut reports) => Ok(reports.remove(0).answer.value),
+            Err(error) => Err(Failure { day: Some(day), input: invocation.input, error }),
+        }
+    }
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Cache::new(Cache::default_dir())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    fn serve(status: &'static str, body: &'static str) -> (String, JoinHandle<String>) {
+        let (base_url, server) = serve_all(vec![(status, body)]);
+        (base_url, thread::spawn(move || server.join().unwrap().remove(0)))
+    }
+
+    fn serve_all(responses: Vec<(&'static str, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                while !request_is_complete(&request) {
+                    let read = stream.read(&mut buffer).unwrap();
+                    if read == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buffer[.. read]);
+                }
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+                requests.push(String::from_utf8(request).unwrap());
+            }
+            requests
+        });
+        (base_url, server)
+    }
+
+    fn request_is_complete(request: &[u8]) -> bool {
+        let request = String::from_utf8_lossy(request);
+        match request.split_once("\r\n\r\n") {
+            Some((head, body)) => {
+                let length = head.lines()
+                    .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length: ").map(str::to_string))
+                    .and_then(|length| length.trim().parse::<usize>().ok())
+                    .unwrap_or(0);
+                body.len() >= length
+            }
+            None => false,
+        }
+    }
+
+    #[test]
+    fn it_downloads_the_input_with_the_session_cookie() -> Result<()> {
+        let (base_url, server) = serve("200 OK", "0,3,6\n");
+        let input = Client::new("abc123").with_base_url(base_url).input(15)?;
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2020/day/15/input HTTP/1.1\r\n"));
+        assert!(request.contains("session=abc123"));
+        Ok(assert_eq!(input, "0,3,6\n"))
+    }
+
+    #[test]
+    fn it_explains_a_rejected_session_token() {
+        let (base_url, server) = serve("400 Bad Request", "Puzzle inputs differ by user.");
+        let result = Client::new("expired").with_base_url(base_url).input(11);
+        server.join().unwrap();
+        let expected_message =
+            "request to Advent of Code failed: the session token was rejected; copy a fresh one from the browser's cookies";
+        assert_eq!(result.unwrap_err().to_string(), expected_message)
+    }
+
+    #[test]
+    fn it_submits_the_answer_for_a_part() -> Result<()> {
+        let (base_url, server) = serve("200 OK", "<main><article><p>That's the right answer! ...</p></article></main>");
+        let submission = Client::new("abc123").with_base_url(base_url).submit(13, Part::Two, &Value::Integer(1068781))?;
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2020/day/13/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("level=2&answer=1068781"));
+        Ok(assert_eq!(submission, Submission::Correct))
+    }
+
+    #[test]
+    fn it_waits_and_submits_again_when_answered_too_recently() -> Result<()> {
+        let (base_url, server) = serve_all(vec![
+            ("200 OK", "<article><p>You gave an answer too recently. You have 0s left to wait.</p></article>"),
+            ("200 OK", "<article><p>That's the right answer!</p></article>"),
+        ]);
+        let mut waits = Vec::new();
+        let client = Client::new("abc123").with_base_url(base_url);
+        let submission = client.submit_with_retry(11, Part::One, &Value::Integer(37), |wait| waits.push(wait))?;
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests.iter().all(|request| request.ends_with("level=1&answer=37")));
+        assert_eq!(waits, [Duration::from_secs(0)]);
+        Ok(assert_eq!(submission, Submission::Correct))
+    }
+
+    #[test]
+    fn it_recognizes_rate_limiting() {
+        let page = "<article><p>You gave an answer too recently; you have to wait after submitting an answer \
+                    before trying again.  You have 4m 30s left to wait. <a href=\"/2020/day/11\">[Return to Day 11]</a></p></article>";
+        assert_eq!(Submission::parse(page), Submission::TooSoon { wait: Duration::from_secs(270) })
+    }
+
+    #[test]
+    fn it_passes_along_hints_for_wrong_answers() {
+        let page = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, ... \
+                    Please wait one minute before trying again.</p></article>";
+        let expected_submission = Submission::Incorrect {
+            hint: Some("your answer is too high".to_string()),
+            wait: Some(Duration::from_secs(60)),
+        };
+        assert_eq!(Submission::parse(page), expected_submission)
+    }
+
+    #[test]
+    fn it_caches_inputs_by_day() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("advent_2020-cache-{}", std::process::id()));
+        let (base_url, server) = serve("200 OK", "939\n7,13,x,x,59\n");
+        let client = Client::new("abc123").with_base_url(base_url);
+        let cache = Cache::new(&dir);
+        let path = cache.fetch(&client, 13, false)?;
+        server.join().unwrap();
+        let cached = cache.fetch(&client, 13, false)?;
+        let contents = std::fs::read_to_string(&path);
+        std::fs::remove_dir_all(&dir)?;
+        assert_eq!(path, dir.join("day13.txt"));
+        assert_eq!(cached, path);
+        Ok(assert_eq!(contents?, "939\n7,13,x,x,59\n"))
+    }
+
+    #[test]
+    fn it_solves_the_cached_input_when_no_answer_is_given() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("advent_2020-answer-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join("day13.txt"), "939\n7,13,x,x,59,x,31,19\n")?;
+        let cache = Cache::new(&dir);
+        let registry = crate::solutions::registry();
+        let solved = cache.answer(&registry, 13, Part::One, None).map_err(|failure| failure.error);
+        let missing = cache.answer(&registry, 12, Part::One, None).map(|_| ()).unwrap_err();
+        std::fs::remove_dir_all(&dir)?;
+        assert_eq!(solved?, Value::Integer(295));
+        assert_eq!((missing.day, missing.error.kind()), (Some(12), "io"));
+        assert_eq!(cache.answer(&registry, 13, Part::Two, Some("abc".to_string())).ok(), Some(Value::Text("abc".to_string())));
+        Ok(())
+    }
+}
@@ -0,0 +1,289 @@
+use crate::{
+    error::{Error, Result},
+    input::Source,
+    solutions::{
+        solver::{Part, Registry, Solver},
+        Failure,
+    },
+};
+use serde_json::json;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Statistics {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Stage {
+    Parse,
+    Solve(u8),
+}
+
+pub struct Measurement {
+    pub day: u8,
+    pub stages: Vec<(Stage, Statistics)>,
+}
+
+#[derive(Default)]
+pub struct Summary {
+    pub measurements: Vec<Measurement>,
+    pub failures: Vec<Failure>,
+}
+
+#[derive(Debug, Default)]
+pub struct Baseline {
+    medians: BTreeMap<(u8, Stage), Duration>,
+}
+
+impl Statistics {
+    pub fn from_samples(samples: &[Duration]) -> Option<Statistics> {
+        let mut samples = samples.to_vec();
+        samples.sort();
+        let runs = samples.len();
+        let min = *samples.first()?;
+        let median = if runs.is_multiple_of(2) {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        } else {
+            samples[runs / 2]
+        };
+        let mean = samples.iter().sum::<Duration>() / runs as u32;
+        let variance = samples.iter()
+            .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>() / runs as f64;
+        Some(Statistics {
+            runs,
+            min,
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+impl Stage {
+    pub fn key(&self) -> String {
+        match self {
+            Stage::Parse => "parse".to_string(),
+            Stage::Solve(part) => format!("part{}", part),
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => f.pad("parse"),
+            Stage::Solve(part) => f.pad(&format!("part {}", part)),
+        }
+    }
+}
+
+impl Summary {
+    pub fn exit_code(&self) -> i32 {
+        self.failures.first().map_or(0, |failure| failure.error.exit_code())
+    }
+}
+
+pub fn measure(solver: &dyn Solver, input: &str, parts: &[Part], runs: usize) -> Result<Measurement> {
+    let runs = runs.max(1);
+    let start = Instant::now();
+    let puzzle = solver.parse(input)?;
+    let mut parse_samples = vec![start.elapsed()];
+    for _ in 1 .. runs {
+        let start = Instant::now();
+        solver.parse(input)?;
+        parse_samples.push(start.elapsed());
+    }
+    let mut stages = Vec::new();
+    stages.extend(Statistics::from_samples(&parse_samples).map(|stats| (Stage::Parse, stats)));
+    for part in parts {
+        let mut solve_samples = Vec::with_capacity(runs);
+        for _ in 0 .. runs {
+            let start = Instant::now();
+            puzzle.solve(*part)?;
+            solve_samples.push(start.elapsed());
+        }
+        let stage = Stage::Solve(part.number());
+        stages.extend(Statistics::from_samples(&solve_samples).map(|stats| (stage, stats)));
+    }
+    Ok(Measurement {
+        day: solver.day(),
+        stages,
+    })
+}
+
+pub fn run(
+    registry: &Registry,
+    inputs: &BTreeMap<u8, PathBuf>,
+    days: &[u8],
+    runs: usize,
+    mut on_measurement: impl FnMut(&Measurement),
+) -> Summary {
+    let mut summary = Summary::default();
+    let solvers = registry.iter()
+        .filter(|solver| days.is_empty() || days.contains(&solver.day()));
+    for solver in solvers {
+        let input = match inputs.get(&solver.day()) {
+            Some(input) => Source::Path(input.clone()),
+            None => continue,
+        };
+        let measurement = input.read()
+            .and_then(|data| measure(solver, &data, solver.parts(), runs));
+        match measurement {
+            Ok(measurement) => {
+                on_measurement(&measurement);
+                summary.measurements.push(measurement);
+            }
+            Err(error) => summary.failures.push(Failure {
+                day: Some(solver.day()),
+                input,
+                error,
+            }),
+        }
+    }
+    summary
+}
+
+impl Baseline {
+    pub fn load(path: impl AsRef<Path>) -> Result<Baseline> {
+        let contents = std::fs::read_to_string(path)?;
+        let source: serde_json::Value = serde_json::from_str(&contents)
+            .map_err(|err| Error::parse_at(err.line(), err.column(), err))?;
+        let days = source.as_object()
+            .ok_or_else(|| Error::parse(1, "expected an object of days"))?;
+        let mut baseline = Baseline::default();
+        for (key, stages) in days {
+            let day = key.strip_prefix("day")
+                .and_then(|day| day.parse::<u8>().ok())
+                .ok_or_else(|| Error::parse(1, format!("expected a day such as day11, found {}", key)))?;
+            for (name, stats) in stages.as_object().into_iter().flatten() {
+                let stage = match name.as_str() {
+                    "parse" => Stage::Parse,
+                    "part1" => Stage::Solve(1),
+                    "part2" => Stage::Solve(2),
+                    _ => Err(Error::parse(1, format!("unexpected stage {} for {}", name, key)))?,
+                };
+                let median = stats["median_ns"].as_u64()
+                    .ok_or_else(|| Error::parse(1, format!("{}.{} is missing median_ns", key, name)))?;
+                baseline.medians.insert((day, stage), Duration::from_nanos(median));
+            }
+        }
+        Ok(baseline)
+    }
+
+    pub fn save(path: impl AsRef<Path>, measurements: &[Measurement]) -> Result<()> {
+        let mut days = serde_json::Map::new();
+        for measurement in measurements {
+            let stages: serde_json::Map<_, _> = measurement.stages.iter()
+                .map(|(stage, stats)| {
+                    (stage.key(), json!({
+                        "runs": stats.runs,
+                        "min_ns": stats.min.as_nanos() as u64,
+                        "median_ns": stats.median.as_nanos() as u64,
+                        "mean_ns": stats.mean.as_nanos() as u64,
+                        "stddev_ns": stats.stddev.as_nanos() as u64,
+                    }))
+                })
+                .collect();
+            days.insert(format!("day{}", measurement.day), stages.into());
+        }
+        let contents = serde_json::to_string_pretty(&days)
+            .expect("benchmark results are always serializable");
+        Ok(std::fs::write(path, contents + "\n")?)
+    }
+
+    pub fn median(&self, day: u8, stage: Stage) -> Option<Duration> {
+        self.medians.get(&(day, stage)).copied()
+    }
+
+    pub fn change(&self, day: u8, stage: Stage, stats: &Statistics) -> Option<f64> {
+        let baseline = self.median(day, stage)?.as_secs_f64();
+        if baseline > 0. {
+            Some((stats.median.as_secs_f64() - baseline) / baseline * 100.)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn it_summarizes_the_samples() {
+        let stats = Statistics::from_samples(&millis(&[8, 2, 6, 4])).unwrap();
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, Duration::from_millis(2));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        assert!((stats.stddev.as_secs_f64() - 5e-6f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn the_median_of_an_odd_number_of_samples_is_the_middle_sample() {
+        let stats = Statistics::from_samples(&millis(&[9, 1, 3])).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(3));
+    }
+
+    #[test]
+    fn there_are_no_statistics_without_samples() {
+        assert_eq!(Statistics::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn it_round_trips_a_baseline() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("advent_2020-baseline-{}.json", std::process::id()));
+        let stats = Statistics::from_samples(&millis(&[4, 6])).unwrap();
+        let measurement = Measurement {
+            day: 14,
+            stages: vec![(Stage::Parse, stats), (Stage::Solve(2), stats)],
+        };
+        Baseline::save(&path, &[measurement])?;
+        let baseline = Baseline::load(&path);
+        std::fs::remove_file(&path)?;
+        let baseline = baseline?;
+        assert_eq!(baseline.median(14, Stage::Solve(2)), Some(Duration::from_millis(5)));
+        Ok(assert_eq!(baseline.median(14, Stage::Solve(1)), None))
+    }
+
+    #[test]
+    fn it_measures_the_selected_days_with_inputs() -> Result<()> {
+        let inputs: BTreeMap<u8, PathBuf> = [
+            (13, Path::new(env!("CARGO_MANIFEST_DIR")).join("src/solutions/day13/example.txt")),
+            (14, PathBuf::from("missing/day14.txt")),
+            (15, Path::new(env!("CARGO_MANIFEST_DIR")).join("src/solutions/day15/example.txt")),
+        ].iter().cloned().collect();
+        let mut measured = Vec::new();
+        let summary = run(&crate::solutions::registry(), &inputs, &[13, 14], 2, |measurement| measured.push(measurement.day));
+        assert_eq!(measured, [13]);
+        let stages: Vec<Stage> = summary.measurements[0].stages.iter().map(|(stage, _)| *stage).collect();
+        assert_eq!(stages, [Stage::Parse, Stage::Solve(1), Stage::Solve(2)]);
+        assert_eq!(summary.failures.iter().map(|failure| failure.day).collect::<Vec<_>>(), [Some(14)]);
+        assert_eq!(summary.exit_code(), 3);
+        Ok(assert_eq!(summary.failures[0].error.kind(), "io"))
+    }
+
+    #[test]
+    fn it_compares_medians_against_the_baseline() {
+        let mut baseline = Baseline::default();
+        baseline.medians.insert((11, Stage::Parse), Duration::from_millis(10));
+        let stats = Statistics::from_samples(&millis(&[12])).unwrap();
+        let change = baseline.change(11, Stage::Parse, &stats).unwrap();
+        assert!((change - 20.).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,157 @@
+use std::{fmt, io};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Parse { location: Location, message: String },
+    Unsolvable(String),
+    ResourceLimit(String),
+    Cancelled(String),
+    Http(String),
+    Usage(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Location {
+    pub line: usize,
+    pub column: Option<usize>,
+}
+
+impl Error {
+    pub fn parse(line: usize, message: impl fmt::Display) -> Error {
+        Error::Parse {
+            location: Location { line, column: None },
+            message: message.to_string(),
+        }
+    }
+
+    pub fn parse_at(line: usize, column: usize, message: impl fmt::Display) -> Error {
+        Error::Parse {
+            location: Location { line, column: Some(column) },
+            message: message.to_string(),
+        }
+    }
+
+    pub fn unsolvable(message: impl fmt::Display) -> Error {
+        Error::Unsolvable(message.to_string())
+    }
+
+    pub fn resource_limit(message: impl fmt::Display) -> Error {
+        Error::ResourceLimit(message.to_string())
+    }
+
+    pub fn cancelled(message: impl fmt::Display) -> Error {
+        Error::Cancelled(message.to_string())
+    }
+
+    pub fn http(message: impl fmt::Display) -> Error {
+        Error::Http(message.to_string())
+    }
+
+    pub fn usage(message: impl fmt::Display) -> Error {
+        Error::Usage(message.to_string())
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::Io(_) => "io",
+            Error::Parse { .. } => "parse",
+            Error::Unsolvable(_) => "unsolvable",
+            Error::ResourceLimit(_) => "resource_limit",
+            Error::Cancelled(_) => "cancelled",
+            Error::Http(_) => "http",
+            Error::Usage(_) => "usage",
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Io(_) => 3,
+            Error::Parse { .. } => 4,
+            Error::Unsolvable(_) => 5,
+            Error::ResourceLimit(_) => 6,
+            Error::Cancelled(_) => 130,
+            Error::Http(_) => 7,
+            Error::Usage(_) => 2,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "I/O error: {}", err),
+            Error::Parse { location, message } => {
+                write!(f, "parse error at {}: {}", location, message)
+            }
+            Error::Unsolvable(message) => write!(f, "the input is unsolvable: {}", message),
+            Error::ResourceLimit(message) => write!(f, "resource limit exceeded: {}", message),
+            Error::Cancelled(message) => write!(f, "cancelled: {}", message),
+            Error::Http(message) => write!(f, "request to Advent of Code failed: {}", message),
+            Error::Usage(message) => write!(f, "invalid usage: {}", message),
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.column {
+            Some(column) => write!(f, "line {}, column {}", self.line, column),
+            None => write!(f, "line {}", self.line),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors_report_their_location() {
+        let expected_message = "parse error at line 3, column 7: unexpected character";
+        let err = Error::parse_at(3, 7, "unexpected character");
+        assert_eq!(err.to_string(), expected_message)
+    }
+
+    #[test]
+    fn parse_errors_can_omit_the_column() {
+        let expected_message = "parse error at line 2: missing schedule";
+        let err = Error::parse(2, "missing schedule");
+        assert_eq!(err.to_string(), expected_message)
+    }
+
+    #[test]
+    fn each_kind_of_error_has_a_distinct_exit_code() {
+        let errors = [
+            Error::from(io::Error::new(io::ErrorKind::NotFound, "missing")),
+            Error::parse(1, "bad"),
+            Error::unsolvable("no answer"),
+            Error::resource_limit("too big"),
+            Error::cancelled("interrupted"),
+            Error::http("offline"),
+            Error::usage("no such command"),
+        ];
+        let mut codes: Vec<i32> = errors.iter().map(Error::exit_code).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), errors.len());
+        assert!(codes.iter().all(|code| *code > 1));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{
+    error::{Error, Result},
+    solutions::{
+        solver::{Example, Part, Value},
+        Report,
+    },
+};
+use std::{collections::HashMap, path::Path};
+
+#[derive(Debug, Default)]
+pub struct Expectations {
+    answers: HashMap<(u8, Part), Value>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict<'a> {
+    Pass,
+    Fail { expected: &'a Value },
+    Unchecked,
+}
+
+impl Expectations {
+    pub fn load(path: impl AsRef<Path>) -> Result<Expectations> {
+        Expectations::parse(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn parse(source: &str) -> Result<Expectations> {
+        let table = match source.parse::<toml::Value>() {
+            Ok(toml::Value::Table(table)) => table,
+            Ok(_) => Err(Error::parse(1, "expected a table of days"))?,
+            Err(err) => match err.line_col() {
+                Some((line, column)) => Err(Error::parse_at(line + 1, column + 1, err))?,
+                None => Err(Error::parse(1, err))?,
+            },
+        };
+        let mut expectations = Expectations::default();
+        for (key, parts) in table {
+            let day = key.strip_prefix("day")
+                .and_then(|day| day.parse::<u8>().ok())
+                .ok_or_else(|| Error::parse(line_of(source, &key, None), format!("expected a day such as [day11], found [{}]", key)))?;
+            let parts = parts.as_table()
+                .ok_or_else(|| Error::parse(line_of(source, &key, None), format!("expected {} to be a table of parts", key)))?;
+            for (name, value) in parts {
+                let part = match name.as_str() {
+                    "part1" => Part::One,
+                    "part2" => Part::Two,
+                    _ => Err(Error::parse(line_of(source, &key, Some(name)), format!("expected part1 or part2, found {}", name)))?,
+                };
+                let value = match value {
+                    toml::Value::Integer(value) => Value::from(*value),
+                    toml::Value::String(value) => Value::from(value.clone()),
+                    _ => Err(Error::parse(line_of(source, &key, Some(name)), format!("expected {}.{} to be a number or a string", key, name)))?,
+                };
+                expectations.answers.insert((day, part), value);
+            }
+        }
+        Ok(expectations)
+    }
+
+    pub fn from_examples(day: u8, examples: &[Example]) -> Expectations {
+        let answers = examples.iter()
+            .map(|example| ((day, example.part), Value::from(example.expected.to_string())))
+            .collect();
+        Expectations { answers }
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&Value> {
+        self.answers.get(&(day, part))
+    }
+
+    pub fn check(&self, report: &Report) -> Verdict<'_> {
+        match self.get(report.day, report.part) {
+            Some(expected) if expected.to_string() == report.answer.value.to_string() => Verdict::Pass,
+            Some(expected) => Verdict::Fail { expected },
+            None => Verdict::Unchecked,
+        }
+    }
+}
+
+fn line_of(source: &str, table: &str, key: Option<&str>) -> usize {
+    let dotted = key.map(|key| format!("{}.{}", table, key));
+    let mut current = None;
+    let mut table_line = None;
+    for (index, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if let Some(header) = line.strip_prefix('[').and_then(|line| line.split(']').next()) {
+            current = Some(header.trim().trim_matches('"'));
+            if current == Some(table) {
+                table_line = table_line.or(Some(index + 1));
+            }
+            continue;
+        }
+        let name = line.split('=').next().map(|name| name.trim().trim_matches('"'));
+        if current.is_none() && name == Some(table) {
+            table_line = table_line.or(Some(index + 1));
+        }
+        let in_table = current == Some(table) && name == key;
+        if key.is_some() && (in_table || (current.is_none() && name == dotted.as_deref())) {
+            return index + 1;
+        }
+    }
+    table_line.unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solutions::report;
+
+    #[test]
+    fn it_reads_answers_by_day_and_part() -> Result<()> {
+        let expectations = Expectations::parse("[day11]\npart1 = 37\npart2 = \"26\"\n")?;
+        assert_eq!(expectations.get(11, Part::One), Some(&Value::Integer(37)));
+        assert_eq!(expectations.get(11, Part::Two), Some(&Value::Text("26".to_string())));
+        Ok(assert_eq!(expectations.get(12, Part::One), None))
+    }
+
+    #[test]
+    fn it_checks_reports_against_the_expected_answers() -> Result<()> {
+        let expectations = Expectations::parse("[day13]\npart1 = 295\npart2 = \"1068781\"\n")?;
+        assert_eq!(expectations.check(&report(13, Part::One, 295u64)), Verdict::Pass);
+        assert_eq!(expectations.check(&report(13, Part::Two, 1068781u64)), Verdict::Pass);
+        assert_eq!(
+            expectations.check(&report(13, Part::One, 296u64)),
+            Verdict::Fail { expected: &Value::Integer(295) }
+        );
+        Ok(assert_eq!(expectations.check(&report(14, Part::One, 1u64)), Verdict::Unchecked))
+    }
+
+    #[test]
+    fn it_reports_where_an_unknown_part_is_defined() {
+        let expected_message = "parse error at line 3: expected part1 or part2, found part3";
+        let result = Expectations::parse("[day11]\npart1 = 37\npart3 = 26\n");
+        assert_eq!(result.unwrap_err().to_string(), expected_message)
+    }
+
+    #[test]
+    fn it_reports_an_invalid_value_in_the_table_it_belongs_to() {
+        let expected_message = "parse error at line 5: expected day12.part1 to be a number or a string";
+        let result = Expectations::parse("[day11]\npart1 = 37\n\n[day12]\npart1 = true\n");
+        assert_eq!(result.unwrap_err().to_string(), expected_message)
+    }
+}
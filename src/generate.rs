@@ -0,0 +1,259 @@
+use crate::{
+    error::{Error, Result},
+    solutions::day14::MAX_FLOATING_BITS,
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use std::fmt::Write;
+
+// Any six primes below 1000 multiply to less than 2^60, so part 2's timestamp always fits in a u64.
+pub const MAX_BUSES: usize = 6;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Settings {
+    pub size: usize,
+    pub seed: u64,
+    pub max_floating_bits: usize,
+}
+
+pub fn generate(day: u8, settings: &Settings) -> Result<String> {
+    let mut rng = StdRng::seed_from_u64(settings.seed);
+    let size = settings.size;
+    if size == 0 {
+        Err(Error::usage("the size must be at least 1"))?
+    }
+    match day {
+        11 => Ok(seat_grid(&mut rng, size, size)),
+        12 => Ok(navigation_script(&mut rng, size)),
+        13 if size > MAX_BUSES => Err(Error::usage(format!(
+            "day 13 inputs have at most {} buses so the winning timestamp fits in 64 bits",
+            MAX_BUSES
+        ))),
+        13 => Ok(bus_schedule(&mut rng, size)),
+        14 if settings.max_floating_bits > MAX_FLOATING_BITS => Err(Error::usage(format!(
+            "day 14 masks have at most {} floating bits",
+            MAX_FLOATING_BITS
+        ))),
+        14 => Ok(comporter_program(&mut rng, size, settings.max_floating_bits)),
+        15 => Ok(counting_game_seed(&mut rng, size)),
+        _ => Err(Error::usage(format!(
+            "there is no input generator for day {}; inputs can be generated for days 11 to 15",
+            day
+        ))),
+    }
+}
+
+pub fn seat_grid(rng: &mut impl Rng, width: usize, height: usize) -> String {
+    let mut grid = String::with_capacity((width + 1) * height);
+    for _ in 0 .. height {
+        grid.extend((0 .. width).map(|_| if rng.gen_bool(0.7) { 'L' } else { '.' }));
+        grid.push('\n');
+    }
+    grid
+}
+
+pub fn navigation_script(rng: &mut impl Rng, actions: usize) -> String {
+    let mut script = String::new();
+    for _ in 0 .. actions {
+        let _ = match rng.gen_range(0 .. 7) {
+            0 => writeln!(script, "N{}", rng.gen_range(1 ..= 100)),
+            1 => writeln!(script, "S{}", rng.gen_range(1 ..= 100)),
+            2 => writeln!(script, "E{}", rng.gen_range(1 ..= 100)),
+            3 => writeln!(script, "W{}", rng.gen_range(1 ..= 100)),
+            4 => writeln!(script, "L{}", 90 * rng.gen_range(1 ..= 3)),
+            5 => writeln!(script, "R{}", 90 * rng.gen_range(1 ..= 3)),
+            _ => writeln!(script, "F{}", rng.gen_range(1 ..= 100)),
+        };
+    }
+    script
+}
+
+pub fn bus_schedule(rng: &mut impl Rng, buses: usize) -> String {
+    let mut primes = primes_below(1000);
+    primes.shuffle(rng);
+    let ids = primes.into_iter().take(buses);
+    let mut schedule = Vec::new();
+    for id in ids {
+        if !schedule.is_empty() {
+            schedule.extend((0 .. rng.gen_range(0 ..= 3)).map(|_| "x".to_string()));
+        }
+        schedule.push(id.to_string());
+    }
+    format!("{}\n{}\n", rng.gen_range(1 ..= 1_000_000), schedule.join(","))
+}
+
+pub fn comporter_program(rng: &mut impl Rng, statements: usize, max_floating_bits: usize) -> String {
+    let mut program = String::new();
+    for index in 0 .. statements {
+        if index % 4 == 0 {
+            let mut mask: Vec<char> = (0 .. 36).map(|_| if rng.gen_bool(0.5) { '0' } else { '1' }).collect();
+            let floating_bits = rng.gen_range(0 ..= max_floating_bits);
+            for offset in rand::seq::index::sample(rng, 36, floating_bits) {
+                mask[offset] = 'X';
+            }
+            let _ = writeln!(program, "mask = {}", mask.into_iter().collect::<String>());
+        } else {
+            let _ = writeln!(program, "mem[{}] = {}", rng.gen_range(0 .. 1 << 16), rng.gen_range(0 .. 1u64 << 36));
+        }
+    }
+    program
+}
+
+pub fn counting_game_seed(rng: &mut impl Rng, numbers: usize) -> String {
+    let seed: Vec<String> = rand::seq::index::sample(rng, numbers.max(100), numbers)
+        .into_iter()
+        .map(|number| number.to_string())
+        .collect();
+    format!("{}\n", seed.join(","))
+}
+
+fn primes_below(limit: u64) -> Vec<u64> {
+    (2 .. limit)
+        .filter(|candidate| (2 ..).take_while(|divisor| divisor * divisor <= *candidate).all(|divisor| candidate % divisor != 0))
+        .collect()
+}
+
+#[cfg(test)]
+pub mod strategies {
+    use proptest::{collection::vec, prelude::*, sample::subsequence};
+
+    pub fn seat_grid() -> impl Strategy<Value = String> {
+        (1 .. 16usize, 1 .. 16usize)
+            .prop_flat_map(|(width, height)| (Just(width), vec(prop_oneof![Just("L"), Just(".")], width * height)))
+            .prop_map(|(width, cells)| cells.chunks(width).map(|row| row.concat() + "\n").collect())
+    }
+
+    pub fn navigation_script() -> impl Strategy<Value = String> {
+        let action = prop_oneof![
+            ("[NSEWF]", 0 .. 1000u16).prop_map(|(action, value)| format!("{}{}", action, value)),
+            ("[LR]", 0 .. 4u16).prop_map(|(action, turns)| format!("{}{}", action, turns * 90)),
+        ];
+        vec(action, 0 .. 50).prop_map(|actions| actions.into_iter().map(|action| action + "\n").collect())
+    }
+
+    pub fn bus_schedule() -> impl Strategy<Value = (u64, Vec<Option<u64>>)> {
+        let primes = vec![2u64, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61];
+        (1 .. 1_000_000u64, subsequence(primes, 1 ..= 8))
+            .prop_flat_map(|(earliest, ids)| {
+                let gaps = vec(0 .. 3usize, ids.len());
+                (Just(earliest), Just(ids), gaps)
+            })
+            .prop_map(|(earliest, ids, gaps)| {
+                let mut schedule = Vec::new();
+                for (id, gap) in ids.into_iter().zip(gaps) {
+                    if !schedule.is_empty() {
+                        schedule.extend((0 .. gap).map(|_| None));
+                    }
+                    schedule.push(Some(id));
+                }
+                (earliest, schedule)
+            })
+    }
+
+    pub fn comporter_program(max_floating_bits: usize) -> impl Strategy<Value = String> {
+        let mask = (vec(prop_oneof![Just("0"), Just("1")], 36), subsequence((0 .. 36).collect::<Vec<usize>>(), 0 ..= max_floating_bits))
+            .prop_map(|(mut bits, floating)| {
+                for offset in floating {
+                    bits[offset] = "X";
+                }
+                format!("mask = {}\n", bits.concat())
+            });
+        let write = (0 .. 1usize << 16, 0 .. 1u64 << 36).prop_map(|(address, value)| format!("mem[{}] = {}\n", address, value));
+        vec((mask, vec(write, 0 .. 4)), 1 .. 8).prop_map(|blocks| {
+            blocks.into_iter().map(|(mask, writes)| mask + &writes.concat()).collect()
+        })
+    }
+
+    pub fn counting_game_seed() -> impl Strategy<Value = Vec<i32>> {
+        subsequence((0 .. 50).collect::<Vec<i32>>(), 1 ..= 8).prop_shuffle()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate, strategies, Settings, MAX_BUSES};
+    use crate::{
+        error::Result,
+        solutions::{day15::counting_game::counting_game_iter, registry, solver::Part},
+    };
+    use proptest::prelude::*;
+
+    fn solve(day: u8, input: &str, part: Part) -> Result<String> {
+        let registry = registry();
+        let solver = registry.get(day).unwrap();
+        Ok(solver.run(input, part)?.value.to_string())
+    }
+
+    #[test]
+    fn the_generated_inputs_can_be_solved() -> Result<()> {
+        let settings = Settings { size: 12, seed: 2020, max_floating_bits: 6 };
+        for day in [11, 12, 14].iter().copied() {
+            let input = generate(day, &settings)?;
+            solve(day, &input, Part::One)?;
+            solve(day, &input, Part::Two)?;
+        }
+        let buses = generate(13, &Settings { size: MAX_BUSES, ..settings })?;
+        solve(13, &buses, Part::One)?;
+        solve(13, &buses, Part::Two)?;
+        Ok(assert!(solve(15, &generate(15, &settings)?, Part::One).is_ok()))
+    }
+
+    #[test]
+    fn the_same_seed_generates_the_same_input() -> Result<()> {
+        let settings = Settings { size: 20, seed: 7, max_floating_bits: 9 };
+        Ok(assert_eq!(generate(14, &settings)?, generate(14, &settings)?))
+    }
+
+    #[test]
+    fn it_refuses_inputs_it_cannot_generate() {
+        let settings = Settings { size: 20, seed: 7, max_floating_bits: 9 };
+        let kind = |day, settings| generate(day, &settings).unwrap_err().kind();
+        assert_eq!(kind(1, settings), "usage");
+        assert_eq!(kind(13, Settings { size: MAX_BUSES + 1, ..settings }), "usage");
+        assert_eq!(kind(14, Settings { max_floating_bits: 37, ..settings }), "usage");
+        assert_eq!(kind(11, Settings { size: 0, ..settings }), "usage")
+    }
+
+    proptest! {
+        #[test]
+        fn seat_grids_settle_with_every_filter(grid in strategies::seat_grid()) {
+            prop_assert!(solve(11, &grid, Part::One).is_ok());
+            prop_assert!(solve(11, &grid, Part::Two).is_ok());
+        }
+
+        #[test]
+        fn navigation_scripts_parse_in_both_modes(script in strategies::navigation_script()) {
+            prop_assert!(solve(12, &script, Part::One).is_ok());
+            prop_assert!(solve(12, &script, Part::Two).is_ok());
+        }
+
+        #[test]
+        fn the_winning_timestamp_lines_up_every_bus((earliest, schedule) in strategies::bus_schedule()) {
+            let ids: Vec<String> = schedule.iter().map(|id| id.map_or("x".to_string(), |id| id.to_string())).collect();
+            let input = format!("{}\n{}\n", earliest, ids.join(","));
+            let timestamp: u64 = solve(13, &input, Part::Two).unwrap().parse().unwrap();
+            for (offset, id) in schedule.iter().enumerate() {
+                if let Some(id) = id {
+                    prop_assert_eq!((timestamp + offset as u64) % id, 0);
+                }
+            }
+        }
+
+        #[test]
+        fn programs_with_bounded_floating_bits_stay_within_limits(program in strategies::comporter_program(8)) {
+            prop_assert!(solve(14, &program, Part::One).is_ok());
+            prop_assert!(solve(14, &program, Part::Two).is_ok());
+        }
+
+        #[test]
+        fn the_counting_game_matches_a_naive_game(seed in strategies::counting_game_seed()) {
+            let mut spoken = seed.clone();
+            while spoken.len() < 200 {
+                let last = *spoken.last().unwrap();
+                let previous = spoken[.. spoken.len() - 1].iter().rposition(|number| *number == last);
+                spoken.push(previous.map_or(0, |turn| (spoken.len() - 1 - turn) as i32));
+            }
+            let game: Vec<i32> = counting_game_iter(&seed).take(200).collect();
+            prop_assert_eq!(game, spoken);
+        }
+    }
+}
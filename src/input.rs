@@ -0,0 +1,138 @@
+use crate::{
+    aoc::Cache,
+    error::{Error, Result},
+};
+use clap::Clap;
+use std::{
+    fmt,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    Path(PathBuf),
+    Cached { day: u8, path: PathBuf },
+    Stdin,
+    Text(String),
+    Examples,
+}
+
+#[derive(Clap)]
+pub struct InputArgs {
+    #[clap(
+        about = "The file containing the puzzle input, or - to read it from stdin \
+                (defaults to the input downloaded with aoc fetch)"
+    )]
+    input: Option<PathBuf>,
+    #[clap(long, conflicts_with = "input", about = "The puzzle input itself instead of a file")]
+    input_text: Option<String>,
+    #[clap(
+        long,
+        conflicts_with_all = &["input", "input-text"],
+        about = "Run the puzzle's own examples and compare them against their expected answers"
+    )]
+    example: bool,
+}
+
+impl Source {
+    pub fn read(&self) -> Result<String> {
+        let raw = match self {
+            Source::Path(path) => std::fs::read_to_string(path)?,
+            Source::Cached { day, path } => std::fs::read_to_string(path).map_err(|err| match err.kind() {
+                io::ErrorKind::NotFound => io::Error::new(
+                    err.kind(),
+                    format!("the input for day {} has not been downloaded yet (try aoc fetch {})", day, day),
+                ),
+                _ => err,
+            })?,
+            Source::Stdin => {
+                let mut raw = String::new();
+                std::io::stdin().read_to_string(&mut raw)?;
+                raw
+            }
+            Source::Text(text) => text.clone(),
+            Source::Examples => Err(Error::unsolvable("the examples are embedded in each solver"))?,
+        };
+        Ok(normalize(&raw))
+    }
+}
+
+impl From<PathBuf> for Source {
+    fn from(path: PathBuf) -> Self {
+        if path.as_os_str() == "-" {
+            Source::Stdin
+        } else {
+            Source::Path(path)
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Path(path) | Source::Cached { path, .. } => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "-"),
+            Source::Text(_) => write!(f, "<input-text>"),
+            Source::Examples => write!(f, "<examples>"),
+        }
+    }
+}
+
+impl InputArgs {
+    pub fn source(self, day: u8) -> Source {
+        match (self.input, self.input_text) {
+            _ if self.example => Source::Examples,
+            (_, Some(text)) => Source::Text(text),
+            (Some(path), None) => Source::from(path),
+            (None, None) => Source::Cached {
+                day,
+                path: Cache::default().path(day),
+            },
+        }
+    }
+}
+
+pub fn normalize(raw: &str) -> String {
+    let mut normalized = String::with_capacity(raw.len());
+    for line in raw.trim_end().lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_converts_crlf_line_endings() {
+        let expected_input = "939\n7,13,x,x,59\n";
+        assert_eq!(normalize("939\r\n7,13,x,x,59\r\n"), expected_input)
+    }
+
+    #[test]
+    fn it_strips_trailing_whitespace_and_blank_lines() {
+        let expected_input = "F10\nN3\n\nF7\n";
+        assert_eq!(normalize("F10  \nN3\t\n\nF7\n\n\n"), expected_input)
+    }
+
+    #[test]
+    fn it_ends_the_input_with_a_newline() {
+        let expected_input = "0,3,6\n";
+        assert_eq!(normalize("0,3,6"), expected_input)
+    }
+
+    #[test]
+    fn a_dash_reads_from_stdin() {
+        assert_eq!(Source::from(PathBuf::from("-")), Source::Stdin);
+        assert_eq!(Source::from(PathBuf::from("day11.txt")), Source::Path("day11.txt".into()));
+    }
+
+    #[test]
+    fn inline_text_is_read_as_is() -> Result<()> {
+        let source = Source::Text("0,3,6".to_string());
+        Ok(assert_eq!(source.read()?, "0,3,6\n"))
+    }
+}
@@ -0,0 +1,24 @@
+#![allow(clippy::unit_arg)]
+
+pub mod all;
+pub mod aoc;
+pub mod bench;
+pub mod error;
+pub mod expectations;
+pub mod generate;
+pub mod input;
+pub mod logging;
+pub mod man;
+pub mod progress;
+pub mod repl;
+pub mod solutions;
+pub mod watch;
+
+pub use error::{Error, Result};
+pub use input::Source;
+pub use progress::Progress;
+pub use solutions::{
+    day1, day11, day12, day13, day14, day15, day2, day3, day4, day5, day6, day7,
+    solver::{Answer, Part, Puzzle, Registry, Solver, Value},
+    Invocation, Report, Summary,
+};
@@ -0,0 +1,20 @@
+use crate::error::Result;
+use std::{fs::File, path::Path, sync::Arc};
+use tracing::Level;
+
+pub fn init(verbose: u64, trace_file: Option<&Path>) -> Result<()> {
+    let level = match (verbose, trace_file) {
+        (0, None) => return Ok(()),
+        (0, Some(_)) | (1, _) => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+    let subscriber = tracing_subscriber::fmt().with_max_level(level);
+    match trace_file {
+        Some(path) => {
+            let file = Arc::new(File::create(path)?);
+            subscriber.with_writer(file).with_ansi(false).init()
+        }
+        None => subscriber.with_writer(std::io::stderr).init(),
+    }
+    Ok(())
+}
@@ -1,17 +1,705 @@
-use clap::Clap;
-
-mod solutions;
+use advent_2020::{
+    all,
+    aoc::{self, Cache, Client, Submission},
+    bench::{self, Baseline, Measurement},
+    expectations::{Expectations, Verdict},
+    generate::{self as generator, Settings},
+    logging, man, progress,
+    repl::Session,
+    solutions::{self, Failure},
+    watch::{self, Change},
+    Error, Invocation, Part, Progress, Registry, Report, Solver, Source, Summary, Value,
+};
+use clap::{App, AppSettings, Clap, FromArgMatches, IntoApp};
+use clap_generate::{
+    generate,
+    generators::{Bash, Fish, Zsh},
+};
+use serde_json::json;
+use std::path::PathBuf;
 
 #[derive(Clap)]
 #[clap(author, about, version)]
 struct Options {
+    #[clap(arg_enum, long, global = true, default_value = "text", about = "The format used to print results")]
+    format: Format,
+    #[clap(long, global = true, about = "Keep running and solve again whenever the input changes")]
+    watch: bool,
+    #[clap(
+        short,
+        long,
+        global = true,
+        parse(from_occurrences),
+        about = "Print what the solvers are doing to stderr (-v for progress, -vv for every step)"
+    )]
+    verbose: u64,
+    #[clap(long, global = true, about = "Write the trace events to a file instead of stderr")]
+    trace_file: Option<PathBuf>,
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+const BIN_NAME: &str = "advent_2020";
+
+const EXIT_CODES: &[(i32, &str)] = &[
+    (0, "The solution ran successfully"),
+    (1, "An answer did not match its expected value"),
+    (2, "The command line arguments were invalid"),
+    (3, "The input could not be read"),
+    (4, "The input could not be parsed"),
+    (5, "The input has no solution"),
+    (6, "Solving the input would exceed a resource limit"),
+    (7, "A request to Advent of Code failed"),
+    (130, "The run was interrupted with Ctrl-C"),
+];
+
+#[derive(Clap, Clone, Copy)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Clap)]
+enum Command {
+    #[clap(about = "List the available solutions")]
+    List,
+    #[clap(about = "Run every available solution against the inputs in a directory")]
+    All(All),
+    #[clap(about = "Download inputs from and submit answers to adventofcode.com")]
+    Aoc(Aoc),
+    #[clap(about = "Explore a day's model interactively")]
+    Repl(Repl),
+    #[clap(about = "Print a shell completion script")]
+    Completions(Completions),
+    #[clap(about = "Print the manual page in roff format")]
+    Man,
+    #[clap(about = "Print a random input for day 11 to 15, for stress testing the solutions")]
+    Generate(Generate),
+    #[clap(about = "Measure how long each solution takes to parse its input and solve each part")]
+    Bench(Bench),
+}
+
+#[derive(Clap)]
+struct All {
+    #[clap(long, about = "The directory containing the inputs (named day11.txt, day12.txt, …)")]
+    inputs: PathBuf,
+    #[clap(long, about = "A TOML file of known-good answers to check against (e.g., [day11] part1 = 37)")]
+    expect: Option<PathBuf>,
+}
+
+#[derive(Clap)]
+struct Aoc {
+    #[clap(long, env = "AOC_SESSION", hide_env_values = true, about = "The session cookie from adventofcode.com")]
+    session: Option<String>,
+    #[clap(long, hidden = true, default_value = aoc::BASE_URL)]
+    base_url: String,
     #[clap(subcommand)]
-    solution: solutions::Solution
+    command: AocCommand,
+}
+
+#[derive(Clap)]
+enum AocCommand {
+    #[clap(about = "Download inputs into the cache (set AOC_CACHE_DIR to change where it lives)")]
+    Fetch(Fetch),
+    #[clap(about = "Submit an answer, solving the cached input if no answer is given")]
+    Submit(Submit),
+}
+
+#[derive(Clap)]
+struct Fetch {
+    #[clap(required = true, about = "The days to download")]
+    days: Vec<u8>,
+    #[clap(long, about = "Download the input again even if it is already cached")]
+    force: bool,
+}
+
+#[derive(Clap)]
+struct Submit {
+    #[clap(about = "The day the answer is for")]
+    day: u8,
+    #[clap(possible_values = &["1", "2"], about = "The part the answer is for")]
+    part: u8,
+    #[clap(about = "The answer to submit")]
+    answer: Option<String>,
+    #[clap(long, about = "Wait and try again when answers were submitted too recently")]
+    wait: bool,
+}
+
+#[derive(Clap)]
+struct Completions {
+    #[clap(arg_enum, about = "The shell to generate completions for")]
+    shell: Shell,
+}
+
+#[derive(Clap, Clone, Copy)]
+enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+#[derive(Clap)]
+struct Generate {
+    #[clap(about = "The day to generate an input for (11 to 15)")]
+    day: u8,
+    #[clap(long, default_value = "100", about = "How big the input is (grid width, number of actions, buses (at most 6), statements or starting numbers)")]
+    size: usize,
+    #[clap(long, default_value = "0", about = "The seed for the random number generator")]
+    seed: u64,
+    #[clap(long, default_value = "9", about = "The most floating bits a day 14 mask may have")]
+    max_floating_bits: usize,
+}
+
+#[derive(Clap)]
+struct Repl {
+    #[clap(about = "The day to load when the REPL starts")]
+    day: Option<u8>,
+    #[clap(requires = "day", about = "The input to load when the REPL starts")]
+    input: Option<PathBuf>,
+    #[clap(requires = "input", about = "The mode to load the input in (e.g., los, waypoint or two)")]
+    mode: Option<String>,
+}
+
+#[derive(Clap)]
+struct Bench {
+    #[clap(long, about = "The directory containing the inputs (named day11.txt, day12.txt, …)")]
+    inputs: PathBuf,
+    #[clap(long = "day", about = "Only benchmark the given day (may be repeated)")]
+    days: Vec<u8>,
+    #[clap(long, default_value = "10", about = "How many times to run each stage")]
+    runs: usize,
+    #[clap(long, about = "Save the results to a baseline file for later comparison")]
+    save_baseline: Option<PathBuf>,
+    #[clap(long, about = "Compare the results against a previously saved baseline file")]
+    baseline: Option<PathBuf>,
 }
 
 fn main() {
-    let opts = Options::parse();
-    opts.solution.run().unwrap_or_else(|err| {
-        println!("Something went wrong running the solution: {}", err);
+    let registry = solutions::registry();
+    let matches = app(&registry).get_matches();
+    let opts = Options::from_arg_matches(&matches);
+    let format = opts.format;
+    let invocation = matches.subcommand()
+        .and_then(|(command, matches)| registry.invocation(command, matches));
+    let progress = Progress::new();
+    let interrupted = progress.clone();
+    let _ = ctrlc::set_handler(move || {
+        if interrupted.is_cancelled() {
+            std::process::exit(Error::cancelled("interrupted").exit_code());
+        }
+        interrupted.cancel();
     });
+    if let Err(err) = logging::init(opts.verbose, opts.trace_file.as_deref()) {
+        print_error(None, opts.trace_file.clone().map(Source::Path).as_ref(), &err, format);
+        std::process::exit(err.exit_code());
+    }
+    if opts.watch && !(matches!(opts.command, Some(Command::All(_))) || invocation.is_some()) {
+        fail(Error::usage("--watch can only be used with the all command or a day"), format);
+    }
+    match opts.command {
+        Some(Command::List) => {
+            for solver in registry.iter() {
+                print_solver(solver, format);
+            }
+        }
+        Some(Command::All(all)) => {
+            if opts.watch {
+                let mut paths = vec![all.inputs.clone()];
+                paths.extend(all.expect.clone());
+                watch(paths, &progress, format, || run_all(&all, &registry, &progress, format))
+            }
+            std::process::exit(run_all(&all, &registry, &progress, format).exit_code())
+        }
+        Some(Command::Aoc(options)) => std::process::exit(run_aoc(options, &registry, format)),
+        Some(Command::Completions(options)) => {
+            let mut app = app(&registry);
+            let stdout = &mut std::io::stdout();
+            match options.shell {
+                Shell::Bash => generate::<Bash, _>(&mut app, BIN_NAME, stdout),
+                Shell::Zsh => generate::<Zsh, _>(&mut app, BIN_NAME, stdout),
+                Shell::Fish => generate::<Fish, _>(&mut app, BIN_NAME, stdout),
+            }
+        }
+        Some(Command::Man) => print!("{}", man::render(&app(&registry), env!("CARGO_PKG_VERSION"), EXIT_CODES)),
+        Some(Command::Generate(options)) => {
+            let settings = Settings {
+                size: options.size,
+                seed: options.seed,
+                max_floating_bits: options.max_floating_bits,
+            };
+            match generator::generate(options.day, &settings) {
+                Ok(input) => print!("{}", input),
+                Err(err) => fail(err, format),
+            }
+        }
+        Some(Command::Repl(options)) => {
+            let mut session = Session::new();
+            if let (Some(day), Some(input)) = (options.day, &options.input) {
+                match session.load(day, input, options.mode.as_deref()) {
+                    Ok(state) => println!("{}", state),
+                    Err(err) => print_error(None, Some(&Source::Path(input.clone())), &err, format),
+                }
+            }
+            let stdin = std::io::stdin();
+            if let Err(err) = session.run(stdin.lock(), std::io::stdout(), std::io::stderr()) {
+                fail(err, format);
+            }
+        }
+        Some(Command::Bench(options)) => {
+            let inputs = solutions::discover_inputs(&options.inputs).unwrap_or_else(|err| {
+                print_error(None, Some(&Source::Path(options.inputs.clone())), &err, format);
+                std::process::exit(err.exit_code());
+            });
+            let baseline = match &options.baseline {
+                Some(path) => Baseline::load(path).unwrap_or_else(|err| {
+                    print_error(None, Some(&Source::Path(path.clone())), &err, format);
+                    std::process::exit(err.exit_code());
+                }),
+                None => Baseline::default(),
+            };
+            if let Format::Text = format {
+                println!(
+                    "{:>3}  {:<6}  {:>4}  {:>12}  {:>12}  {:>12}  {:>12}  {:>7}",
+                    "Day", "Stage", "Runs", "Min", "Median", "Mean", "Stddev", "Change"
+                );
+            }
+            let summary = bench::run(&registry, &inputs, &options.days, options.runs, |measurement| {
+                print_measurement(measurement, &baseline, format)
+            });
+            if let Some(path) = &options.save_baseline {
+                if let Err(err) = Baseline::save(path, &summary.measurements) {
+                    print_error(None, Some(&Source::Path(path.clone())), &err, format);
+                    std::process::exit(err.exit_code());
+                }
+            }
+            for failure in &summary.failures {
+                print_failure(failure, format);
+            }
+            std::process::exit(summary.exit_code());
+        }
+        None => {
+            let invocation = invocation.expect("every other command is a registered day");
+            if opts.watch {
+                match &invocation.input {
+                    Source::Path(path) | Source::Cached { path, .. } => watch(vec![path.clone()], &progress, format, || run_solution(&invocation, &registry, &progress, format)),
+                    _ => fail(Error::usage("--watch needs an input file to watch"), format),
+                }
+            }
+            std::process::exit(run_solution(&invocation, &registry, &progress, format).exit_code())
+        }
+    }
+}
+
+fn app(registry: &Registry) -> App<'static> {
+    let exit_codes: Vec<String> = EXIT_CODES.iter()
+        .map(|(code, meaning)| format!("    {:<5}{}", code, meaning))
+        .collect();
+    // The solvers' commands are 'static, so the help text built here has to be as well.
+    let after_help = Box::leak(format!("EXIT CODES:\n{}", exit_codes.join("\n")).into_boxed_str());
+    Options::into_app()
+        .after_help(&*after_help)
+        .name(BIN_NAME)
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommands(registry.commands())
+}
+
+fn run_aoc(options: Aoc, registry: &Registry, format: Format) -> i32 {
+    let session = match options.session {
+        Some(session) => session,
+        None => fail(Error::usage("pass --session or set AOC_SESSION to the session cookie from adventofcode.com"), format),
+    };
+    let client = Client::new(session).with_base_url(options.base_url);
+    let cache = Cache::default();
+    match options.command {
+        AocCommand::Fetch(fetch) => {
+            let fetched = cache.fetch_all(&client, &fetch.days, fetch.force, |day, path| match format {
+                Format::Text => println!("day{}\t{}", day, path.display()),
+                Format::Json => println!("{}", json!({ "day": day, "path": path })),
+            });
+            match fetched {
+                Ok(()) => 0,
+                Err(failure) => {
+                    match (format, failure.day) {
+                        (Format::Text, Some(day)) => {
+                            eprintln!("Something went wrong downloading the input for day {}: {}", day, failure.error)
+                        }
+                        _ => print_failure(&failure, format),
+                    }
+                    failure.error.exit_code()
+                }
+            }
+        }
+        AocCommand::Submit(submit) => {
+            let part = if submit.part == 1 { Part::One } else { Part::Two };
+            let answer = match cache.answer(registry, submit.day, part, submit.answer) {
+                Ok(answer) => answer,
+                Err(failure) => {
+                    print_failure(&failure, format);
+                    return failure.error.exit_code();
+                }
+            };
+            let submitted = if submit.wait {
+                client.submit_with_retry(submit.day, part, &answer, |wait| {
+                    if let Format::Text = format {
+                        eprintln!("Answered too recently; trying again in {}s.", wait.as_secs());
+                    }
+                })
+            } else {
+                client.submit(submit.day, part, &answer)
+            };
+            match submitted {
+                Ok(submission) => {
+                    print_submission(submit.day, part, &answer, &submission, format);
+                    submission.exit_code()
+                }
+                Err(err) => {
+                    match format {
+                        Format::Text => eprintln!("Something went wrong submitting the answer for day {}: {}", submit.day, err),
+                        Format::Json => print_error(Some(submit.day), Some(&Source::Text(answer.to_string())), &err, format),
+                    }
+                    err.exit_code()
+                }
+            }
+        }
+    }
+}
+
+fn print_submission(day: u8, part: Part, answer: &Value, submission: &Submission, format: Format) {
+    match format {
+        Format::Text => {
+            let prefix = format!("Day {} part {}: {}", day, part, answer);
+            match submission {
+                Submission::Correct => println!("{} is correct!", prefix),
+                Submission::Incorrect { hint, wait } => {
+                    let hint = hint.as_ref().map_or(String::new(), |hint| format!(" ({})", hint));
+                    let wait = wait.map_or(String::new(), |wait| format!(" Wait {}s before trying again.", wait.as_secs()));
+                    println!("{} is not correct{}.{}", prefix, hint, wait)
+                }
+                Submission::TooSoon { wait } => {
+                    println!("{} was not checked: answered too recently. Wait {}s or pass --wait.", prefix, wait.as_secs())
+                }
+                Submission::AlreadySolved => println!("Day {} part {} has already been solved.", day, part),
+                Submission::Unrecognized(text) => println!("{} got an unexpected response: {}", prefix, text),
+            }
+        }
+        Format::Json => {
+            let (result, hint, wait, text) = match submission {
+                Submission::Correct => ("correct", None, None, None),
+                Submission::Incorrect { hint, wait } => ("incorrect", hint.as_deref(), *wait, None),
+                Submission::TooSoon { wait } => ("too_soon", None, Some(*wait), None),
+                Submission::AlreadySolved => ("already_solved", None, None, None),
+                Submission::Unrecognized(text) => ("unrecognized", None, None, Some(text.as_str())),
+            };
+            println!(
+                "{}",
+                json!({
+                    "day": day,
+                    "part": part.number(),
+                    "answer": value_json(answer),
+                    "result": result,
+                    "hint": hint,
+                    "wait_seconds": wait.map(|wait| wait.as_secs()),
+                    "response": text,
+                })
+            )
+        }
+    }
+}
+
+fn run_all(all: &All, registry: &Registry, progress: &Progress, format: Format) -> Summary {
+    let (inputs, expectations) = match all::load(&all.inputs, all.expect.as_deref()) {
+        Ok(loaded) => loaded,
+        Err(failure) => {
+            print_failure(&failure, format);
+            return Summary { failures: vec![failure], ..Summary::default() };
+        }
+    };
+    if let Format::Text = format {
+        let header = format!(
+            "{:>3}  {:<24}  {:>4}  {:<20}  {:>12}  {}",
+            "Day", "Name", "Part", "Answer", "Time",
+            if all.expect.is_some() { "Check" } else { "" }
+        );
+        println!("{}", header.trim_end());
+    }
+    let summary = show_progress(progress, format, || {
+        all::run(registry, &inputs, &expectations, progress, |event| match event {
+            all::Event::Solved { solver, report, verdict } => print_row(solver, report, verdict, format),
+            all::Event::Failed { solver, .. } => {
+                if let Format::Text = format {
+                    println!("{:>3}  {:<24}  {:>4}  FAILED", solver.day(), solver.name(), "-");
+                }
+            }
+        })
+    });
+    if let Format::Text = format {
+        if !summary.missing.is_empty() {
+            let days: Vec<String> = summary.missing.iter().map(u8::to_string).collect();
+            println!("\nSkipped days without inputs in {}: {}", all.inputs.display(), days.join(", "));
+        }
+    }
+    if !summary.failures.is_empty() {
+        if let Format::Text = format {
+            let attempted = registry.iter().count() - summary.missing.len();
+            eprintln!("\n{} of {} days failed:", summary.failures.len(), attempted);
+        }
+        for failure in &summary.failures {
+            print_failure(failure, format);
+        }
+    } else if summary.mismatches > 0 {
+        if let Format::Text = format {
+            let noun = if summary.mismatches == 1 { "answer does" } else { "answers do" };
+            eprintln!("\n{} {} not match the expected values.", summary.mismatches, noun);
+        }
+    }
+    summary
+}
+
+fn run_solution(invocation: &Invocation, registry: &Registry, progress: &Progress, format: Format) -> Summary {
+    let summary = show_progress(progress, format, || {
+        invocation.run_summary(registry, progress, &Expectations::default(), |report, verdict| {
+            if invocation.input == Source::Examples {
+                print_example(report, verdict, format);
+            } else {
+                print_report(report, format);
+            }
+        })
+    });
+    for failure in &summary.failures {
+        print_failure(failure, format);
+    }
+    summary
+}
+
+fn show_progress<T>(progress: &Progress, format: Format, work: impl FnOnce() -> T) -> T {
+    match format {
+        Format::Text => progress::show_while(progress, work),
+        Format::Json => work(),
+    }
+}
+
+fn watch(paths: Vec<PathBuf>, progress: &Progress, format: Format, mut run: impl FnMut() -> Summary) -> ! {
+    let watched = Source::Path(paths[0].clone());
+    let result = watch::run(paths, progress, || run().reports, |event| match (event, format) {
+        (watch::Event::Watching(paths), Format::Text) => {
+            let paths: Vec<String> = paths.iter().map(|path| path.display().to_string()).collect();
+            eprintln!("\nWatching {} for changes (press Ctrl-C to stop)…", paths.join(", "));
+        }
+        (watch::Event::Changed(changed), Format::Text) => {
+            let changed: Vec<String> = changed.iter().map(|path| path.display().to_string()).collect();
+            eprintln!("{} changed, running again.\n", changed.join(", "));
+        }
+        (watch::Event::Changes(changes), _) => print_changes(changes, format),
+        _ => (),
+    });
+    match result {
+        Ok(never) => match never {},
+        Err(err) => {
+            if let (Format::Text, Error::Cancelled(_)) = (format, &err) {
+                std::process::exit(err.exit_code())
+            }
+            print_error(None, Some(&watched), &err, format);
+            std::process::exit(err.exit_code())
+        }
+    }
+}
+
+fn print_changes(changes: &[Change], format: Format) {
+    let show = |value: &Option<Value>| value.as_ref().map_or("-".to_string(), Value::to_string);
+    match format {
+        Format::Text if changes.is_empty() => println!("\nNo answers changed."),
+        Format::Text => {
+            println!("\nChanged answers:");
+            for change in changes {
+                println!("  day {} part {}: {} -> {}", change.day, change.part, show(&change.old), show(&change.new));
+            }
+        }
+        Format::Json => {
+            for change in changes {
+                println!(
+                    "{}",
+                    json!({
+                        "change": {
+                            "day": change.day,
+                            "part": change.part,
+                            "old": change.old.as_ref().map(value_json),
+                            "new": change.new.as_ref().map(value_json),
+                        }
+                    })
+                )
+            }
+        }
+    }
+}
+
+fn print_solver(solver: &dyn Solver, format: Format) {
+    let parts: Vec<u8> = solver.parts().iter().map(Part::number).collect();
+    match format {
+        Format::Text => {
+            let parts: Vec<String> = parts.iter().map(u8::to_string).collect();
+            println!("day{}\t{} (parts {})", solver.day(), solver.name(), parts.join(", "))
+        }
+        Format::Json => println!(
+            "{}",
+            json!({
+                "day": solver.day(),
+                "name": solver.name(),
+                "parts": parts,
+            })
+        ),
+    }
+}
+
+fn print_report(report: &Report, format: Format) {
+    match format {
+        Format::Text => println!("{}", report.answer.description),
+        Format::Json => println!("{}", report_json(report)),
+    }
+}
+
+fn print_row(solver: &dyn Solver, report: &Report, verdict: &Verdict, format: Format) {
+    match format {
+        Format::Text => {
+            let check = match verdict {
+                Verdict::Pass => "ok".to_string(),
+                Verdict::Fail { expected } => format!("FAILED (expected {})", expected),
+                Verdict::Unchecked => String::new(),
+            };
+            let row = format!(
+                "{:>3}  {:<24}  {:>4}  {:<20}  {:>9.3} ms  {}",
+                report.day,
+                solver.name(),
+                report.part,
+                report.answer.value,
+                report.elapsed.as_secs_f64() * 1000.,
+                check
+            );
+            println!("{}", row.trim_end())
+        }
+        Format::Json => println!("{}", checked_report_json(report, verdict)),
+    }
+}
+
+fn print_example(report: &Report, verdict: &Verdict, format: Format) {
+    match format {
+        Format::Text => {
+            let (expected, status) = match verdict {
+                Verdict::Pass => (report.answer.value.to_string(), "ok"),
+                Verdict::Fail { expected } => (expected.to_string(), "FAILED"),
+                Verdict::Unchecked => ("?".to_string(), "unchecked"),
+            };
+            println!(
+                "Part {}: expected {}, got {} ({})\n    {}",
+                report.part, expected, report.answer.value, status, report.answer.description
+            )
+        }
+        Format::Json => println!("{}", checked_report_json(report, verdict)),
+    }
+}
+
+fn print_measurement(measurement: &Measurement, baseline: &Baseline, format: Format) {
+    let millis = |duration: std::time::Duration| duration.as_secs_f64() * 1000.;
+    for (stage, stats) in &measurement.stages {
+        let change = baseline.change(measurement.day, *stage, stats);
+        match format {
+            Format::Text => {
+                let change = change.map_or(String::new(), |change| format!("{:+.1}%", change));
+                let row = format!(
+                    "{:>3}  {:<6}  {:>4}  {:>9.3} ms  {:>9.3} ms  {:>9.3} ms  {:>9.3} ms  {:>7}",
+                    measurement.day,
+                    stage,
+                    stats.runs,
+                    millis(stats.min),
+                    millis(stats.median),
+                    millis(stats.mean),
+                    millis(stats.stddev),
+                    change
+                );
+                println!("{}", row.trim_end())
+            }
+            Format::Json => println!(
+                "{}",
+                json!({
+                    "day": measurement.day,
+                    "stage": stage.key(),
+                    "runs": stats.runs,
+                    "min_ms": millis(stats.min),
+                    "median_ms": millis(stats.median),
+                    "mean_ms": millis(stats.mean),
+                    "stddev_ms": millis(stats.stddev),
+                    "change_percent": change,
+                })
+            ),
+        }
+    }
+}
+
+fn report_json(report: &Report) -> serde_json::Value {
+    json!({
+        "day": report.day,
+        "part": report.part.number(),
+        "answer": value_json(&report.answer.value),
+        "description": report.answer.description,
+        "input": report.input.to_string(),
+        "elapsed_ms": report.elapsed.as_secs_f64() * 1000.,
+    })
+}
+
+fn checked_report_json(report: &Report, verdict: &Verdict) -> serde_json::Value {
+    let mut record = report_json(report);
+    match verdict {
+        Verdict::Pass => record["check"] = json!({ "status": "pass" }),
+        Verdict::Fail { expected } => record["check"] = json!({ "status": "fail", "expected": value_json(expected) }),
+        Verdict::Unchecked => (),
+    }
+    record
+}
+
+fn value_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Integer(value) => json!(value),
+        Value::Text(value) => json!(value),
+    }
+}
+
+fn print_failure(failure: &Failure, format: Format) {
+    print_error(failure.day, Some(&failure.input), &failure.error, format)
+}
+
+fn fail(err: Error, format: Format) -> ! {
+    print_error(None, None, &err, format);
+    std::process::exit(err.exit_code())
+}
+
+fn print_error(day: Option<u8>, input: Option<&Source>, err: &Error, format: Format) {
+    match format {
+        Format::Text => match (day, input, err) {
+            (_, _, Error::Usage(message)) => eprintln!("error: {}", message),
+            (Some(day), _, Error::Cancelled(message)) => eprintln!("Interrupted the solution for day {}: {}", day, message),
+            (Some(day), _, _) => eprintln!("Something went wrong running the solution for day {}: {}", day, err),
+            (None, Some(input), _) => eprintln!("Something went wrong reading {}: {}", input, err),
+            (None, None, _) => eprintln!("error: {}", err),
+        },
+        Format::Json => {
+            let location = match err {
+                Error::Parse { location, .. } => json!({
+                    "line": location.line,
+                    "column": location.column,
+                }),
+                _ => json!(null),
+            };
+            eprintln!(
+                "{}",
+                json!({
+                    "day": day,
+                    "input": input.map(Source::to_string),
+                    "error": {
+                        "kind": err.kind(),
+                        "message": err.to_string(),
+                        "location": location,
+                    },
+                })
+            )
+        }
+    }
 }
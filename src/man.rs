@@ -0,0 +1,110 @@
+use clap::{App, Arg, ArgSettings};
+use std::fmt::Write;
+
+pub fn render(app: &App, version: &str, exit_codes: &[(i32, &str)]) -> String {
+    let name = app.get_name();
+    let mut page = String::new();
+    let _ = writeln!(page, ".TH {} 1 \"\" \"{} {}\"", escape(&name.to_uppercase()), escape(name), escape(version));
+    page.push_str(".SH NAME\n");
+    let _ = writeln!(page, "{} \\- {}", escape(name), escape(app.get_about().unwrap_or_default()));
+    page.push_str(".SH SYNOPSIS\n");
+    let _ = writeln!(page, "\\fB{}\\fR [OPTIONS] <COMMAND>", escape(name));
+    page.push_str(".SH OPTIONS\n");
+    options(app, &mut page);
+    page.push_str(".SH COMMANDS\n");
+    commands(app, &[name], &mut page);
+    page.push_str(".SH EXIT STATUS\n");
+    for (code, meaning) in exit_codes {
+        let _ = writeln!(page, ".TP\n{}\n{}", code, escape(meaning));
+    }
+    page
+}
+
+fn commands(app: &App, parents: &[&str], page: &mut String) {
+    for command in app.get_subcommands() {
+        let mut path = parents.to_vec();
+        path.push(command.get_name());
+        let _ = writeln!(page, ".SS {}", escape(&path[1 ..].join(" ")));
+        if let Some(about) = command.get_about() {
+            let _ = writeln!(page, "{}", escape(about));
+        }
+        let mut synopsis = vec!["[OPTIONS]".to_string()];
+        synopsis.extend(command.get_positionals().map(usage));
+        if command.get_subcommands().next().is_some() {
+            synopsis.push("<COMMAND>".to_string());
+        }
+        let _ = writeln!(
+            page,
+            ".PP\n\\fB{}\\fR {}",
+            escape(&path.join(" ")),
+            escape(&synopsis.join(" "))
+        );
+        options(command, page);
+        commands(command, &path, page);
+    }
+}
+
+fn options(app: &App, page: &mut String) {
+    for arg in app.get_arguments().filter(|arg| !arg.is_set(ArgSettings::Hidden)) {
+        let mut names = Vec::new();
+        names.extend(arg.get_short().map(|short| format!("\\fB\\-{}\\fR", short)));
+        names.extend(arg.get_long().map(|long| format!("\\fB\\-\\-{}\\fR", escape(long))));
+        let mut term = names.join(", ");
+        if arg.get_index().is_some() {
+            term = escape(&usage(arg));
+        } else if arg.is_set(ArgSettings::TakesValue) {
+            let _ = write!(term, " <{}>", escape(arg.get_name()));
+        }
+        let _ = writeln!(page, ".TP\n{}", term);
+        let mut about = arg.get_about().unwrap_or_default().to_string();
+        if let Some(values) = arg.get_possible_values() {
+            let _ = write!(about, " [possible values: {}]", values.join(", "));
+        }
+        let _ = writeln!(page, "{}", escape(about.trim()));
+    }
+}
+
+fn usage(arg: &Arg) -> String {
+    if arg.is_set(ArgSettings::Required) {
+        format!("<{}>", arg.get_name())
+    } else {
+        format!("[{}]", arg.get_name())
+    }
+}
+
+fn escape(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('-', "\\-");
+    match escaped.chars().next() {
+        Some('.') | Some('\'') => format!("\\&{}", escaped),
+        _ => escaped,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_renders_commands_and_their_options() {
+        let app = App::new("advent")
+            .about("Solves puzzles")
+            .arg(Arg::new("format").long("format").takes_value(true).about("The output format"))
+            .subcommand(App::new("day11").about("Seating System").arg(Arg::new("input").required(true).index(1)));
+        let page = render(&app, "1.0.0", &[(0, "Success")]);
+        assert!(page.starts_with(".TH ADVENT 1 \"\" \"advent 1.0.0\"\n.SH NAME\nadvent \\- Solves puzzles\n"));
+        assert!(page.contains(".TP\n\\fB\\-\\-format\\fR <format>\nThe output format\n"));
+        assert!(page.contains(".SS day11\nSeating System\n.PP\n\\fBadvent day11\\fR [OPTIONS] <input>\n"));
+        assert!(page.ends_with(".SH EXIT STATUS\n.TP\n0\nSuccess\n"));
+    }
+
+    #[test]
+    fn it_renders_nested_commands_under_their_full_path() {
+        let app = App::new("advent")
+            .subcommand(App::new("aoc")
+                .about("Talks to Advent of Code")
+                .subcommand(App::new("fetch").about("Downloads an input").arg(Arg::new("day").required(true).index(1))));
+        let page = render(&app, "1.0.0", &[]);
+        assert!(page.contains(".SS aoc\nTalks to Advent of Code\n.PP\n\\fBadvent aoc\\fR [OPTIONS] <COMMAND>\n"));
+        assert!(page.contains(".SS aoc fetch\nDownloads an input\n.PP\n\\fBadvent aoc fetch\\fR [OPTIONS] <day>\n"));
+    }
+}
@@ -0,0 +1,164 @@
+use crate::error::{Error, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+#[derive(Clone, Debug, Default)]
+pub struct Progress {
+    state: Arc<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    done: AtomicU64,
+    total: AtomicU64,
+    unit: Mutex<&'static str>,
+    cancelled: AtomicBool,
+}
+
+impl Progress {
+    pub fn new() -> Progress {
+        Progress::default()
+    }
+
+    pub fn start(&self, total: u64, unit: &'static str) {
+        if let Ok(mut current) = self.state.unit.lock() {
+            *current = unit;
+        }
+        self.state.total.store(total, Ordering::Relaxed);
+        self.state.done.store(0, Ordering::Relaxed);
+    }
+
+    pub fn update(&self, done: u64) -> Result<()> {
+        self.state.done.store(done, Ordering::Relaxed);
+        self.check(done)
+    }
+
+    pub fn advance(&self, amount: u64) -> Result<()> {
+        let done = self.state.done.fetch_add(amount, Ordering::Relaxed) + amount;
+        self.check(done)
+    }
+
+    fn check(&self, done: u64) -> Result<()> {
+        if self.is_cancelled() {
+            Err(Error::cancelled(format!(
+                "stopped after {} of {} {}",
+                done,
+                self.total(),
+                self.unit()
+            )))?
+        }
+        Ok(())
+    }
+
+    pub fn finish(&self) {
+        self.state.total.store(0, Ordering::Relaxed);
+        self.state.done.store(0, Ordering::Relaxed);
+    }
+
+    pub fn done(&self) -> u64 {
+        self.state.done.load(Ordering::Relaxed)
+    }
+
+    pub fn total(&self) -> u64 {
+        self.state.total.load(Ordering::Relaxed)
+    }
+
+    pub fn unit(&self) -> &'static str {
+        self.state.unit.lock().map_or("steps", |unit| *unit)
+    }
+
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+pub fn show_while<T>(progress: &Progress, work: impl FnOnce() -> T) -> T {
+    let finished = Arc::new(AtomicBool::new(false));
+    let drawer = {
+        let (progress, finished) = (progress.clone(), finished.clone());
+        thread::spawn(move || {
+            let mut started = None;
+            let mut bar: Option<ProgressBar> = None;
+            while !finished.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(100));
+                if progress.total() == 0 {
+                    started = None;
+                    if let Some(bar) = bar.take() {
+                        bar.finish_and_clear();
+                    }
+                    continue;
+                }
+                if started.get_or_insert_with(Instant::now).elapsed() < Duration::from_millis(500) {
+                    continue;
+                }
+                let bar = bar.get_or_insert_with(|| {
+                    let bar = ProgressBar::new(progress.total());
+                    bar.set_style(
+                        ProgressStyle::default_bar()
+                            .template("{bar:40} {percent:>3}% {pos}/{len} {msg} (ETA {eta})"),
+                    );
+                    bar
+                });
+                bar.set_length(progress.total());
+                bar.set_position(progress.done());
+                bar.set_message(progress.unit());
+            }
+            if let Some(bar) = bar {
+                bar.finish_and_clear();
+            }
+        })
+    };
+    let result = work();
+    finished.store(true, Ordering::Relaxed);
+    let _ = drawer.join();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_the_same_progress() {
+        let progress = Progress::new();
+        let observer = progress.clone();
+        progress.start(30, "turns");
+        progress.update(12).unwrap();
+        assert_eq!((observer.done(), observer.total(), observer.unit()), (12, 30, "turns"));
+    }
+
+    #[test]
+    fn it_advances_by_an_amount() {
+        let progress = Progress::new();
+        progress.start(1024, "memory writes");
+        progress.update(10).unwrap();
+        progress.advance(4).unwrap();
+        assert_eq!(progress.done(), 14);
+    }
+
+    #[test]
+    fn updates_fail_once_the_work_is_cancelled() {
+        let progress = Progress::new();
+        progress.start(30_000_000, "turns");
+        progress.clone().cancel();
+        let expected_message = "cancelled: stopped after 1000000 of 30000000 turns";
+        assert_eq!(progress.update(1_000_000).unwrap_err().to_string(), expected_message)
+    }
+
+    #[test]
+    fn it_returns_the_result_of_the_work_it_shows() {
+        let progress = Progress::new();
+        assert_eq!(show_while(&progress, || 42), 42)
+    }
+}
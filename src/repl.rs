@@ -0,0 +1,355 @@
+use crate::{
+    error::{Error, Result},
+    input::Source,
+    solutions::{
+        day11::{self, WaitingArea, PERSON},
+        day12::{
+            manhattan_distance,
+            ship::{Action, Ship},
+        },
+        day14::comporter::{Comporter, ProgramStatement, Version},
+        day15::counting_game::counting_game_iter,
+    },
+};
+use std::{
+    collections::VecDeque,
+    io::{BufRead, Write},
+    path::Path,
+};
+
+pub const HELP: &str = "\
+Commands:
+    load <day> <path> [mode]  Load a day's input (11: nearby|los, 12: ship|waypoint, 14: one|two)
+    step [n]                  Advance the model by n steps (default 1)
+    show                      Print the current state of the model
+    help                      Print this message
+    quit                      Leave the REPL
+
+Day 12 also accepts actions such as F10 or R90, and day 14 accepts
+statements such as mask = XX10 or mem[8] = 11, which are applied immediately.";
+
+pub const MAX_STEPS: usize = 30_000_000;
+
+#[derive(Default)]
+pub struct Session {
+    model: Option<Model>,
+    line: usize,
+}
+
+enum Model {
+    Seating {
+        area: WaitingArea,
+        line_of_sight: bool,
+        generation: usize,
+        stable: bool,
+    },
+    Navigation {
+        ship: Ship,
+        waypoint: bool,
+        pending: VecDeque<Action>,
+    },
+    Docking {
+        comporter: Comporter,
+        pending: VecDeque<ProgramStatement>,
+    },
+    CountingGame {
+        numbers: Box<dyn Iterator<Item = i32>>,
+        turn: usize,
+        last: Option<i32>,
+    },
+}
+
+impl Session {
+    pub fn new() -> Session {
+        Session::default()
+    }
+
+    pub fn run(&mut self, input: impl BufRead, mut output: impl Write, mut errors: impl Write) -> Result<()> {
+        let mut lines = input.lines();
+        loop {
+            write!(output, "advent> ")?;
+            output.flush()?;
+            let line = match lines.next() {
+                Some(line) => line?,
+                None => return Ok(()),
+            };
+            match line.trim() {
+                "quit" | "exit" => return Ok(()),
+                line => match self.eval(line) {
+                    Ok(result) if result.is_empty() => (),
+                    Ok(result) => writeln!(output, "{}", result)?,
+                    Err(err) => writeln!(errors, "error: {}", err)?,
+                },
+            }
+        }
+    }
+
+    pub fn eval(&mut self, line: &str) -> Result<String> {
+        self.line += 1;
+        let number = self.line;
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => Ok(String::new()),
+            ["help"] => Ok(HELP.to_string()),
+            ["load", day, path, mode @ ..] => {
+                let day = day.parse::<u8>()
+                    .map_err(|_| Error::parse(number, format!("expected a day number, found {}", day)))?;
+                self.load(day, Path::new(path), mode.first().copied())
+            }
+            ["step"] => self.model_mut()?.step(1),
+            ["step", count] => {
+                let count = count.parse()
+                    .map_err(|_| Error::parse(number, format!("expected a number of steps, found {}", count)))?;
+                if count > MAX_STEPS {
+                    Err(Error::usage(format!("step takes at most {} steps at a time", MAX_STEPS)))?
+                }
+                self.model_mut()?.step(count)
+            }
+            ["show"] => Ok(self.model()?.show()),
+            _ => self.model_mut()?.apply(line.trim(), number),
+        }
+    }
+
+    pub fn load(&mut self, day: u8, path: &Path, mode: Option<&str>) -> Result<String> {
+        let input = Source::Path(path.to_path_buf()).read()?;
+        self.model = Some(Model::load(day, &input, mode)?);
+        Ok(self.model()?.show())
+    }
+
+    fn model(&self) -> Result<&Model> {
+        self.model.as_ref().ok_or_else(nothing_loaded)
+    }
+
+    fn model_mut(&mut self) -> Result<&mut Model> {
+        self.model.as_mut().ok_or_else(nothing_loaded)
+    }
+}
+
+impl Model {
+    fn load(day: u8, input: &str, mode: Option<&str>) -> Result<Model> {
+        match (day, mode) {
+            (11, None) | (11, Some("nearby")) | (11, Some("los")) => Ok(Model::Seating {
+                area: WaitingArea::parse(input)?,
+                line_of_sight: mode == Some("los"),
+                generation: 0,
+                stable: false,
+            }),
+            (12, None) | (12, Some("ship")) | (12, Some("waypoint")) => {
+                let waypoint = mode == Some("waypoint");
+                let mut ship = Ship::new();
+                if waypoint {
+                    ship.act(Action::MoveWaypointEast(9));
+                    ship.act(Action::MoveWaypointNorth(1));
+                }
+                Ok(Model::Navigation {
+                    ship,
+                    waypoint,
+                    pending: parse_lines(input, |line| Action::parse(line, waypoint))?,
+                })
+            }
+            (14, None) | (14, Some("one")) | (14, Some("two")) => Ok(Model::Docking {
+                comporter: Comporter::new(if mode == Some("two") { Version::Two } else { Version::One }),
+                pending: parse_lines(input, ProgramStatement::parse)?,
+            }),
+            (15, None) => {
+                let numbers = input.trim()
+                    .split(',')
+                    .map(str::parse)
+                    .collect::<Result<Vec<i32>, _>>()
+                    .map_err(|err| Error::parse(1, err))?;
+                Ok(Model::CountingGame {
+                    numbers: Box::new(counting_game_iter(&numbers)),
+                    turn: 0,
+                    last: None,
+                })
+            }
+            (11, Some(mode)) | (12, Some(mode)) | (14, Some(mode)) | (15, Some(mode)) => {
+                Err(Error::usage(format!("day {} has no {} mode", day, mode)))
+            }
+            _ => Err(Error::usage(format!("day {} has nothing to explore", day))),
+        }
+    }
+
+    fn step(&mut self, count: usize) -> Result<String> {
+        match self {
+            Model::Seating { area, line_of_sight, generation, stable } => {
+                for _ in 0 .. count {
+                    let before = area.clone();
+                    if *line_of_sight {
+                        area.apply_rules(day11::line_of_sight_filter);
+                    } else {
+                        area.apply_rules(day11::nearby_filter);
+                    }
+                    *generation += 1;
+                    *stable = *area == before;
+                    if *stable {
+                        break;
+                    }
+                }
+            }
+            Model::Navigation { ship, pending, .. } => {
+                for _ in 0 .. count {
+                    let action = pending.pop_front().ok_or_else(|| Error::usage("there are no more actions"))?;
+                    ship.act(action);
+                }
+            }
+            Model::Docking { comporter, pending } => {
+                for _ in 0 .. count {
+                    let statement = pending.pop_front().ok_or_else(|| Error::usage("there are no more statements"))?;
+                    comporter.step(&statement);
+                }
+            }
+            Model::CountingGame { numbers, turn, last } => {
+                for _ in 0 .. count {
+                    *last = numbers.next();
+                    *turn += 1;
+                }
+            }
+        }
+        Ok(self.show())
+    }
+
+    fn apply(&mut self, command: &str, line: usize) -> Result<String> {
+        match self {
+            Model::Navigation { ship, waypoint, .. } if is_action(command) => {
+                ship.act(Action::parse(command, *waypoint).map_err(|err| Error::parse(line, err))?)
+            }
+            Model::Docking { comporter, .. } if is_statement(command) => {
+                comporter.step(&ProgramStatement::parse(command).map_err(|err| Error::parse(line, err))?)
+            }
+            _ => Err(Error::parse(line, format!("unknown command: {}; try help", command)))?,
+        }
+        Ok(self.show())
+    }
+
+    fn show(&self) -> String {
+        match self {
+            Model::Seating { area, generation, stable, .. } => {
+                let occupied = area.to_string().bytes().filter(|cell| *cell == PERSON).count();
+                let stable = if *stable { " (stable)" } else { "" };
+                format!("{}\ngeneration {}: {} occupied seats{}", area, generation, occupied, stable)
+            }
+            Model::Navigation { ship, pending, .. } => {
+                let (x, y) = ship.position();
+                let (wx, wy) = ship.waypoint();
+                format!(
+                    "position ({}, {}), orientation {}°, waypoint ({}, {}), manhattan distance {}, {} actions left",
+                    x, y, ship.orientation(), wx, wy, manhattan_distance((x, y)), pending.len()
+                )
+            }
+            Model::Docking { comporter, pending } => {
+                let next = pending.front().map_or("none".to_string(), ToString::to_string);
+                format!(
+                    "sum of memory {}, {} statements left, next {}",
+                    comporter.sum_of_memory(), pending.len(), next
+                )
+            }
+            Model::CountingGame { turn, last, .. } => match last {
+                Some(number) => format!("turn {}: {}", turn, number),
+                None => "the game has not started yet".to_string(),
+            },
+        }
+    }
+}
+
+fn nothing_loaded() -> Error {
+    Error::usage("nothing is loaded yet; try load <day> <path>")
+}
+
+fn parse_lines<T>(input: &str, parse: impl Fn(&str) -> anyhow::Result<T>) -> Result<VecDeque<T>> {
+    input.lines()
+        .enumerate()
+        .map(|(index, line)| parse(line).map_err(|err| Error::parse(index + 1, err)))
+        .collect()
+}
+
+fn is_action(command: &str) -> bool {
+    let mut chars = command.chars();
+    matches!(chars.next(), Some('N' | 'S' | 'E' | 'W' | 'L' | 'R' | 'F'))
+        && chars.next().is_some_and(|c| c.is_ascii_digit())
+}
+
+fn is_statement(command: &str) -> bool {
+    command.starts_with("mask") || command.starts_with("mem[")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(day: u8, input: &str, mode: Option<&str>) -> Session {
+        Session {
+            model: Some(Model::load(day, input, mode).unwrap()),
+            ..Session::default()
+        }
+    }
+
+    #[test]
+    fn it_steps_the_waiting_area_until_it_is_stable() -> Result<()> {
+        let mut session = load(11, include_str!("solutions/day11/example.txt"), None);
+        session.eval("step 10")?;
+        Ok(assert!(session.eval("show")?.ends_with("generation 6: 37 occupied seats (stable)")))
+    }
+
+    #[test]
+    fn it_sails_the_ship_with_typed_and_loaded_actions() -> Result<()> {
+        let mut session = load(12, "F10\nN3\n", None);
+        session.eval("step")?;
+        let expected_state = "position (10, 0), orientation 90°, waypoint (1, 0), manhattan distance 10, 1 actions left";
+        Ok(assert_eq!(session.eval("L90")?, expected_state))
+    }
+
+    #[test]
+    fn it_runs_comporter_statements_one_at_a_time() -> Result<()> {
+        let mut session = load(14, "", Some("one"));
+        session.eval("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X")?;
+        Ok(assert_eq!(session.eval("mem[8] = 11")?, "sum of memory 73, 0 statements left, next none"))
+    }
+
+    #[test]
+    fn it_advances_the_counting_game() -> Result<()> {
+        let mut session = load(15, "0,3,6\n", None);
+        session.eval("step 9")?;
+        Ok(assert_eq!(session.eval("step")?, "turn 10: 0"))
+    }
+
+    #[test]
+    fn it_reads_commands_until_told_to_quit() -> Result<()> {
+        let mut session = load(15, "0,3,6\n", None);
+        let (mut output, mut errors) = (Vec::new(), Vec::new());
+        session.run("step 4\n\nsail\nquit\nstep\n".as_bytes(), &mut output, &mut errors)?;
+        assert_eq!(String::from_utf8_lossy(&output), "advent> turn 4: 0\nadvent> advent> advent> ");
+        let expected_errors = "error: parse error at line 3: unknown command: sail; try help\n";
+        Ok(assert_eq!(String::from_utf8_lossy(&errors), expected_errors))
+    }
+
+    #[test]
+    fn it_asks_for_a_model_before_stepping() {
+        let expected_message = "invalid usage: nothing is loaded yet; try load <day> <path>";
+        let result = Session::new().eval("step");
+        assert_eq!(result.unwrap_err().to_string(), expected_message)
+    }
+
+    #[test]
+    fn it_refuses_to_step_too_far_at_once() {
+        let mut session = load(15, "0,3,6\n", None);
+        let result = session.eval("step 18446744073709551615");
+        assert_eq!(result.unwrap_err().kind(), "usage");
+        assert_eq!(session.eval("show").unwrap(), "the game has not started yet")
+    }
+
+    #[test]
+    fn it_reports_unknown_commands_before_parsing_actions() -> Result<()> {
+        let mut session = load(12, "F10\n", None);
+        session.eval("show")?;
+        let expected_message = "parse error at line 2: unknown command: sail; try help";
+        assert_eq!(session.eval("sail").unwrap_err().to_string(), expected_message);
+        Ok(assert_eq!(session.eval("F1x").unwrap_err().kind(), "parse"))
+    }
+
+    #[test]
+    fn it_reports_the_line_of_an_invalid_input() {
+        let result = Model::load(12, "F10\nN3\nQ7\n", None);
+        assert!(result.err().unwrap().to_string().starts_with("parse error at line 3: "))
+    }
+}
@@ -1,29 +1,318 @@
+use crate::{
+    error::{Error, Result},
+    expectations::{Expectations, Verdict},
+    input::{self, Source},
+    progress::Progress,
+};
 use clap::Clap;
-use std::error::Error;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
+pub mod day1;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod graph;
+pub mod grid;
+pub mod solver;
+
+use solver::{Answer, Example, Part, Registry, Solver};
 
 #[derive(Clap)]
-pub enum Solution {
-    Day11(day11::Solution),
-    Day12(day12::Solution),
-    Day13(day13::Solution),
-    Day14(day14::Solution),
-    Day15(day15::Solution),
-}
-
-impl Solution {
-    pub fn run(self) -> Result<(), Box<dyn Error>> {
-        match self {
-            Solution::Day11(solution) => solution.run(),
-            Solution::Day12(solution) => solution.run(),
-            Solution::Day13(solution) => solution.run(),
-            Solution::Day14(solution) => solution.run(),
-            Solution::Day15(solution) => solution.run(),
+pub struct PartArgs {
+    #[clap(long, possible_values = &["1", "2", "both"], about = "Which part of the puzzle to solve")]
+    part: Option<String>,
+}
+
+pub struct Invocation {
+    pub day: u8,
+    pub input: Source,
+    pub parts: Vec<Part>,
+    solver: Option<Box<dyn Solver>>,
+    checked: bool,
+}
+
+pub struct Report {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Answer,
+    pub input: Source,
+    pub elapsed: Duration,
+}
+
+pub struct Failure {
+    pub day: Option<u8>,
+    pub input: Source,
+    pub error: Error,
+}
+
+#[derive(Default)]
+pub struct Summary {
+    pub reports: Vec<Report>,
+    pub failures: Vec<Failure>,
+    pub mismatches: usize,
+    pub missing: Vec<u8>,
+}
+
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+    registry
+        .register(day1::ReportRepair::new())
+        .register(day2::PasswordPhilosophy::new())
+        .register(day3::TobogganTrajectory::new())
+        .register(day4::PassportProcessing::new())
+        .register(day5::BinaryBoarding::new())
+        .register(day6::CustomCustoms::new())
+        .register(day7::HandyHaversacks::new())
+        .register(day11::SeatingSystem)
+        .register(day12::RainRisk)
+        .register(day13::ShuttleSearch)
+        .register(day14::DockingData)
+        .register(day15::RambunctiousRecitation::new());
+    registry
+}
+
+pub fn discover_inputs(dir: impl AsRef<Path>) -> Result<BTreeMap<u8, PathBuf>> {
+    let mut inputs = BTreeMap::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let day = path.file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix("day"))
+            .and_then(|name| name.strip_suffix(".txt"))
+            .and_then(|day| day.parse::<u8>().ok());
+        if let Some(day) = day {
+            inputs.insert(day, path);
+        }
+    }
+    Ok(inputs)
+}
+
+impl PartArgs {
+    pub fn parts(&self, default: &[Part]) -> Vec<Part> {
+        match self.part.as_deref() {
+            Some("1") => vec![Part::One],
+            Some("2") => vec![Part::Two],
+            Some(_) => vec![Part::One, Part::Two],
+            None => default.to_vec(),
+        }
+    }
+}
+
+impl Summary {
+    pub fn exit_code(&self) -> i32 {
+        match self.failures.first() {
+            Some(failure) => failure.error.exit_code(),
+            None if self.mismatches > 0 => 1,
+            None => 0,
         }
     }
 }
+
+impl Invocation {
+    pub fn new(day: u8, input: impl Into<Source>, parts: &[Part]) -> Invocation {
+        Invocation {
+            day,
+            input: input.into(),
+            parts: parts.to_vec(),
+            solver: None,
+            checked: true,
+        }
+    }
+
+    pub fn with_solver(mut self, solver: impl Solver + 'static) -> Invocation {
+        self.solver = Some(Box::new(solver));
+        self
+    }
+
+    pub fn unchecked(mut self) -> Invocation {
+        self.checked = false;
+        self
+    }
+
+    pub fn run(&self, registry: &Registry) -> Result<Vec<Report>> {
+        let mut reports = Vec::new();
+        self.run_with(registry, &Progress::new(), |report, _| reports.push(report))?;
+        Ok(reports)
+    }
+
+    pub fn run_checked(
+        &self,
+        registry: &Registry,
+        progress: &Progress,
+        expectations: &Expectations,
+        mut on_report: impl FnMut(Report, &Verdict),
+    ) -> Result<()> {
+        self.run_with(registry, progress, |report, example| {
+            let own = example.map(|example| Expectations::from_examples(self.day, std::slice::from_ref(example)));
+            let verdict = own.as_ref().unwrap_or(expectations).check(&report);
+            on_report(report, &verdict)
+        })
+    }
+
+    pub fn run_summary(
+        &self,
+        registry: &Registry,
+        progress: &Progress,
+        expectations: &Expectations,
+        mut on_report: impl FnMut(&Report, &Verdict),
+    ) -> Summary {
+        let mut summary = Summary::default();
+        let result = self.run_checked(registry, progress, expectations, |report, verdict| {
+            if let Verdict::Fail { .. } = verdict {
+                summary.mismatches += 1;
+            }
+            on_report(&report, verdict);
+            summary.reports.push(report);
+        });
+        if let Err(error) = result {
+            summary.failures.push(Failure {
+                day: Some(self.day),
+                input: self.input.clone(),
+                error,
+            });
+        }
+        summary
+    }
+
+    pub fn run_with(
+        &self,
+        registry: &Registry,
+        progress: &Progress,
+        mut on_report: impl FnMut(Report, Option<&Example>),
+    ) -> Result<()> {
+        let solver = match &self.solver {
+            Some(solver) => solver.as_ref(),
+            None => registry
+                .get(self.day)
+                .ok_or_else(|| Error::unsolvable(format!("no solver is registered for day {}", self.day)))?,
+        };
+        if let Source::Examples = self.input {
+            for example in solver.examples().iter().filter(|example| self.parts.contains(&example.part)) {
+                let input = input::normalize(example.input);
+                let checked = Some(example).filter(|_| self.checked);
+                self.solve(solver, &input, &[example.part], progress, &mut |report| on_report(report, checked))?;
+            }
+            return Ok(());
+        }
+        self.solve(solver, &self.input.read()?, &self.parts, progress, &mut |report| on_report(report, None))
+    }
+
+    fn solve(
+        &self,
+        solver: &dyn Solver,
+        data: &str,
+        parts: &[Part],
+        progress: &Progress,
+        on_report: &mut impl FnMut(Report),
+    ) -> Result<()> {
+        let start = Instant::now();
+        let puzzle = solver.parse(data)?;
+        let parse_time = start.elapsed();
+        for part in parts {
+            let start = Instant::now();
+            let answer = puzzle.solve_with(*part, progress);
+            progress.finish();
+            on_report(Report {
+                day: self.day,
+                part: *part,
+                answer: answer?,
+                input: self.input.clone(),
+                elapsed: parse_time + start.elapsed(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub fn report(day: u8, part: Part, value: impl Into<solver::Value>) -> Report {
+    Report {
+        day,
+        part,
+        answer: Answer::new(value, ""),
+        input: Source::Path("input.txt".into()),
+        elapsed: Duration::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_discovers_inputs_named_after_their_day() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("advent_2020-discover-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        for name in ["day11.txt", "day07.txt", "day3.txt", "notes.txt", "day12.json"].iter() {
+            std::fs::write(dir.join(name), "")?;
+        }
+        let inputs = discover_inputs(&dir);
+        std::fs::remove_dir_all(&dir)?;
+        let days: Vec<u8> = inputs?.keys().copied().collect();
+        Ok(assert_eq!(days, [3, 7, 11]))
+    }
+
+    #[test]
+    fn it_selects_parts_from_the_command_line() {
+        let parts = |args: &[&str]| PartArgs::parse_from(args).parts(&[Part::One]);
+        assert_eq!(parts(&["day"]), [Part::One]);
+        assert_eq!(parts(&["day", "--part", "2"]), [Part::Two]);
+        assert_eq!(parts(&["day", "--part", "both"]), [Part::One, Part::Two]);
+    }
+
+    #[test]
+    fn the_embedded_examples_produce_their_expected_answers() -> Result<()> {
+        let registry = registry();
+        for solver in registry.iter() {
+            // Day 15's second part plays thirty million turns, which is too slow for a debug build.
+            let parts: Vec<Part> = [Part::One, Part::Two].iter()
+                .copied()
+                .filter(|part| (solver.day(), *part) != (15, Part::Two))
+                .collect();
+            let mut checked = 0;
+            let invocation = Invocation::new(solver.day(), Source::Examples, &parts);
+            invocation.run_checked(&registry, &Progress::new(), &Expectations::default(), |report, verdict| {
+                assert_eq!(*verdict, Verdict::Pass, "day {} part {} answered {}", report.day, report.part, report.answer.value);
+                checked += 1;
+            })?;
+            let examples = solver.examples().iter().filter(|example| parts.contains(&example.part)).count();
+            assert_eq!(checked, examples, "day {}", solver.day());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn unchecked_examples_have_no_verdict() -> Result<()> {
+        let registry = registry();
+        let invocation = Invocation::new(7, Source::Examples, &[Part::Two]).unchecked();
+        let mut verdicts = Vec::new();
+        invocation.run_checked(&registry, &Progress::new(), &Expectations::default(), |_, verdict| {
+            verdicts.push(*verdict == Verdict::Unchecked)
+        })?;
+        Ok(assert_eq!(verdicts, [true, true]))
+    }
+
+    #[test]
+    fn it_summarizes_a_run() {
+        let registry = registry();
+        let summary = Invocation::new(13, Source::Examples, &[Part::One])
+            .run_summary(&registry, &Progress::new(), &Expectations::default(), |_, _| ());
+        assert_eq!((summary.reports.len(), summary.mismatches, summary.exit_code()), (1, 0, 0));
+        let summary = Invocation::new(14, Source::Text("mask = XX10\n".to_string()), &[Part::One])
+            .run_summary(&registry, &Progress::new(), &Expectations::default(), |_, _| ());
+        assert_eq!(summary.failures.iter().map(|failure| failure.day).collect::<Vec<_>>(), [Some(14)]);
+        assert_eq!(summary.exit_code(), 4)
+    }
+}
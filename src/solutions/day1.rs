@@ -0,0 +1,178 @@
+pub mod k_sum;
+
+use super::{
+    solver::{Answer, Example, Part, Puzzle, Solver},
+    Invocation, PartArgs,
+};
+use crate::{
+    error::{Error, Result},
+    input::InputArgs,
+};
+use clap::{App, ArgMatches, Clap, FromArgMatches, IntoApp};
+use k_sum::k_sum;
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: Part::One,
+        input: include_str!("day1/example.txt"),
+        expected: "514579",
+    },
+    Example {
+        part: Part::Two,
+        input: include_str!("day1/example.txt"),
+        expected: "241861950",
+    },
+];
+
+const TARGET: i64 = 2020;
+
+pub struct ReportRepair {
+    target: i64,
+    entries: Option<usize>,
+}
+
+struct ExpenseReport {
+    entries: Vec<i64>,
+    target: i64,
+    k: Option<usize>,
+}
+
+impl ReportRepair {
+    pub fn new() -> ReportRepair {
+        ReportRepair::default()
+    }
+
+    pub fn with_target(mut self, target: i64) -> ReportRepair {
+        self.target = target;
+        self
+    }
+
+    pub fn with_entries(mut self, entries: usize) -> ReportRepair {
+        self.entries = Some(entries);
+        self
+    }
+}
+
+impl Default for ReportRepair {
+    fn default() -> Self {
+        ReportRepair {
+            target: TARGET,
+            entries: None,
+        }
+    }
+}
+
+impl Solver for ReportRepair {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        "Report Repair"
+    }
+
+    fn command(&self) -> App<'static> {
+        Solution::into_app()
+    }
+
+    fn invocation(&self, matches: &ArgMatches) -> Invocation {
+        Solution::from_arg_matches(matches).invocation()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Puzzle>> {
+        let entries = input.lines()
+            .enumerate()
+            .map(|(index, line)| line.trim().parse().map_err(|err| Error::parse(index + 1, err)))
+            .collect::<Result<_>>()?;
+        Ok(Box::new(ExpenseReport {
+            entries,
+            target: self.target,
+            k: self.entries,
+        }))
+    }
+}
+
+impl Puzzle for ExpenseReport {
+    fn solve(&self, part: Part) -> Result<Answer> {
+        let k = self.k.unwrap_or(match part {
+            Part::One => 2,
+            Part::Two => 3,
+        });
+        if k == 0 {
+            Err(Error::unsolvable("at least one entry has to be chosen"))?
+        }
+        let entries = k_sum(&self.entries, k, self.target)
+            .ok_or_else(|| Error::unsolvable(format!("no {} entries sum to {}", k, self.target)))?;
+        let product = entries.iter()
+            .try_fold(1i64, |product, entry| product.checked_mul(*entry))
+            .ok_or_else(|| Error::resource_limit("the product of the entries does not fit in 64 bits"))?;
+        let factors: Vec<String> = entries.iter().map(|entry| entry.to_string()).collect();
+        Ok(Answer::new(product, format!("{} = {}", factors.join("×"), product)))
+    }
+}
+
+#[derive(Clap)]
+#[clap(name = "day1", about = "Day 1: Report Repair")]
+pub struct Solution {
+    #[clap(flatten)]
+    input: InputArgs,
+    #[clap(flatten)]
+    parts: PartArgs,
+    #[clap(long, default_value = "2020", about = "The number the entries have to sum to")]
+    target: i64,
+    #[clap(
+        short = 'k',
+        long,
+        conflicts_with = "part",
+        about = "How many entries to choose instead of the number for the part"
+    )]
+    entries: Option<usize>,
+}
+
+impl Solution {
+    pub fn invocation(self) -> Invocation {
+        let solver = ReportRepair::new().with_target(self.target);
+        match self.entries {
+            Some(entries) => Invocation::new(1, self.input.source(1), &[Part::One])
+                .with_solver(solver.with_entries(entries)),
+            None => Invocation::new(1, self.input.source(1), &self.parts.parts(&[Part::One, Part::Two]))
+                .with_solver(solver),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_shows_the_entries_that_were_multiplied() -> Result<()> {
+        let answer = ReportRepair::new().run(EXAMPLES[0].input, Part::Two)?;
+        Ok(assert_eq!(answer.description, "366×675×979 = 241861950"))
+    }
+
+    #[test]
+    fn the_target_and_entries_can_be_changed() -> Result<()> {
+        let solver = ReportRepair::new().with_target(1345).with_entries(2);
+        let answer = solver.run(EXAMPLES[0].input, Part::Two)?;
+        Ok(assert_eq!(answer.description, "366×979 = 358314"))
+    }
+
+    #[test]
+    fn it_explains_when_no_entries_sum_to_the_target() {
+        let expected_message = "the input is unsolvable: no 2 entries sum to 7";
+        let result = ReportRepair::new().with_target(7).run(EXAMPLES[0].input, Part::One);
+        assert_eq!(result.err().unwrap().to_string(), expected_message)
+    }
+
+    #[test]
+    fn it_reports_the_line_of_an_invalid_entry() {
+        let expected_message = "parse error at line 2: invalid digit found in string";
+        let result = ReportRepair::new().parse("1721\n97x\n");
+        assert_eq!(result.err().unwrap().to_string(), expected_message)
+    }
+}
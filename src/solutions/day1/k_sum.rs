@@ -0,0 +1,66 @@
+pub fn k_sum(entries: &[i64], k: usize, target: i64) -> Option<Vec<i64>> {
+    let mut sorted = entries.to_vec();
+    sorted.sort_unstable();
+    find(&sorted, k, target as i128)
+}
+
+fn find(sorted: &[i64], k: usize, target: i128) -> Option<Vec<i64>> {
+    match k {
+        0 => Some(Vec::new()).filter(|_| target == 0),
+        1 => sorted.iter().find(|entry| **entry as i128 == target).map(|entry| vec![*entry]),
+        2 => pair(sorted, target),
+        _ => {
+            for (index, first) in sorted.iter().enumerate() {
+                if index > 0 && sorted[index - 1] == *first {
+                    continue;
+                }
+                if let Some(mut rest) = find(&sorted[index + 1 ..], k - 1, target - *first as i128) {
+                    rest.insert(0, *first);
+                    return Some(rest);
+                }
+            }
+            None
+        }
+    }
+}
+
+fn pair(sorted: &[i64], target: i128) -> Option<Vec<i64>> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let (mut low, mut high) = (0, sorted.len() - 1);
+    while low < high {
+        let sum = sorted[low] as i128 + sorted[high] as i128;
+        match sum.cmp(&target) {
+            std::cmp::Ordering::Less => low += 1,
+            std::cmp::Ordering::Greater => high -= 1,
+            std::cmp::Ordering::Equal => return Some(vec![sorted[low], sorted[high]]),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::k_sum;
+
+    const ENTRIES: [i64; 6] = [1721, 979, 366, 299, 675, 1456];
+
+    #[test]
+    fn it_finds_the_pair_and_triple_from_the_example() {
+        assert_eq!(k_sum(&ENTRIES, 2, 2020), Some(vec![299, 1721]));
+        assert_eq!(k_sum(&ENTRIES, 3, 2020), Some(vec![366, 675, 979]));
+    }
+
+    #[test]
+    fn an_entry_is_only_used_once() {
+        assert_eq!(k_sum(&[1010, 5], 2, 2020), None);
+        assert_eq!(k_sum(&[1010, 1010], 2, 2020), Some(vec![1010, 1010]));
+    }
+
+    #[test]
+    fn it_handles_more_entries_than_a_triple() {
+        assert_eq!(k_sum(&ENTRIES, 4, 366 + 299 + 675 + 1456), Some(vec![299, 366, 675, 1456]));
+        assert_eq!(k_sum(&ENTRIES, 7, 2020), None);
+    }
+}
@@ -2,9 +2,33 @@ mod part1;
 mod part2;
 mod waiting_area;
 
-use anyhow::anyhow;
-use clap::Clap;
-use waiting_area::WaitingArea;
+pub use part1::nearby_filter;
+pub use part2::line_of_sight_filter;
+pub use waiting_area::{WaitingArea, FLOOR, PERSON, SEAT};
+
+use super::{
+    solver::{Answer, Example, Part, Puzzle, Solver},
+    Invocation, PartArgs,
+};
+use crate::{
+    error::Result,
+    input::InputArgs,
+};
+use clap::{App, ArgMatches, Clap, FromArgMatches, IntoApp};
+use tracing::debug;
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: Part::One,
+        input: include_str!("day11/example.txt"),
+        expected: "37",
+    },
+    Example {
+        part: Part::Two,
+        input: include_str!("day11/example.txt"),
+        expected: "26",
+    },
+];
 
 pub trait Day11Extensions {
     fn wait_until_stable(&mut self, filter: impl Fn(&mut [u8], usize, usize) + Copy);
@@ -14,39 +38,78 @@ impl Day11Extensions for WaitingArea {
     fn wait_until_stable(&mut self, filter: impl Fn(&mut [u8], usize, usize) + Copy) {
         let mut current_area = self.to_string();
         let mut new_area;
+        let mut generation = 1;
         self.apply_rules(filter);
         while { new_area = self.to_string(); current_area != new_area } {
+            debug!(generation, occupied = new_area.bytes().filter(|cell| *cell == PERSON).count(), "seats changed");
             current_area = new_area;
+            generation += 1;
             self.apply_rules(filter);
         }
+        debug!(generation, "the waiting area is stable");
     }
 }
 
-#[derive(Clap)]
-pub struct Solution {
-    input: std::path::PathBuf,
-    #[clap(short = 'l', long = "los", about = "Use the line-of-sight filter instead of the nearby filter")]
-    line_of_sight_filter: bool,
+pub struct SeatingSystem;
+
+impl Solver for SeatingSystem {
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn name(&self) -> &'static str {
+        "Seating System"
+    }
+
+    fn command(&self) -> App<'static> {
+        Solution::into_app()
+    }
+
+    fn invocation(&self, matches: &ArgMatches) -> Invocation {
+        Solution::from_arg_matches(matches).invocation()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Puzzle>> {
+        let room = WaitingArea::parse(input)?;
+        Ok(Box::new(room))
+    }
 }
 
-impl Solution {
-    pub fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let filter =
-            if self.line_of_sight_filter {
-                part2::line_of_sight_filter
-            } else {
-                part1::nearby_filter
-            };
-        let data = std::fs::read_to_string(&self.input)?;
-        let mut room = WaitingArea::parse(&data)
-            .ok_or(anyhow!("error parsing room data in the input file"))?;
+impl Puzzle for WaitingArea {
+    fn solve(&self, part: Part) -> Result<Answer> {
+        let filter = match part {
+            Part::One => part1::nearby_filter,
+            Part::Two => part2::line_of_sight_filter,
+        };
+        let mut room = self.clone();
         room.wait_until_stable(filter);
         let contents = room.to_string();
         let occupied_seats = contents.chars()
             .filter(|ch| *ch == '#')
             .count();
-        println!("There are {} occupied seats.", occupied_seats);
-        Ok(())
+        Ok(Answer::new(occupied_seats, format!("There are {} occupied seats.", occupied_seats)))
+    }
+}
+
+#[derive(Clap)]
+#[clap(name = "day11", about = "Day 11: Seating System")]
+pub struct Solution {
+    #[clap(flatten)]
+    input: InputArgs,
+    #[clap(flatten)]
+    parts: PartArgs,
+    #[clap(short = 'l', long = "los", conflicts_with = "part", about = "Use the line-of-sight filter instead of the nearby filter")]
+    line_of_sight_filter: bool,
+}
+
+impl Solution {
+    pub fn invocation(self) -> Invocation {
+        let parts = if self.line_of_sight_filter { vec![Part::Two] } else { self.parts.parts(&[Part::One]) };
+        Invocation::new(11, self.input.source(11), &parts)
     }
 }
 
@@ -82,4 +145,11 @@ mod tests {
         waiting_area.wait_until_stable(part1::nearby_filter);
         assert_eq!(waiting_area.to_string(), expected_area_contents);
     }
+
+    #[test]
+    fn it_reports_the_first_row_with_the_wrong_width() {
+        let expected_message = "parse error at line 3: expected a row of 4 cells";
+        let result = SeatingSystem.parse("L.LL\nLLLL\nLLL\nL.LL");
+        assert_eq!(result.err().unwrap().to_string(), expected_message);
+    }
 }
@@ -18,8 +18,8 @@ pub fn line_of_sight_filter(grid: &mut [u8], width: usize, height: usize) {
 }
 
 fn cast_ray(grid: &[u8], row: usize, column: usize, width: usize, height: usize, dx: isize, dy: isize) -> (usize, usize) {
-    let dx_dir = dx >= 0; let dx = dx.abs() as usize;
-    let dy_dir = dy >= 0; let dy = dy.abs() as usize;
+    let dx_dir = dx >= 0; let dx = dx.unsigned_abs();
+    let dy_dir = dy >= 0; let dy = dy.unsigned_abs();
     let mut r = if dx_dir { row.saturating_add(dx) } else { row.wrapping_sub(dx) };
     let mut c = if dy_dir { column.saturating_add(dy) } else { column.wrapping_sub(dy) };
     while r < height && c < width && grid[r * width + c] == FLOOR {
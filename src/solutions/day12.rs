@@ -1,15 +1,106 @@
-use clap::Clap;
+use clap::{App, ArgMatches, Clap, FromArgMatches, IntoApp};
 
 mod part1;
-mod ship;
+pub mod ship;
 
+pub use part1::manhattan_distance;
+
+use super::{
+    solver::{Answer, Example, Part, Puzzle, Solver},
+    Invocation, PartArgs,
+};
+use crate::{
+    error::{Error, Result},
+    input::InputArgs,
+};
 use ship::{Action, Ship};
 
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: Part::One,
+        input: include_str!("day12/example.txt"),
+        expected: "25",
+    },
+    Example {
+        part: Part::Two,
+        input: include_str!("day12/example.txt"),
+        expected: "286",
+    },
+];
+
+pub struct RainRisk;
+
+struct NavigationInstructions {
+    actions: Vec<Action>,
+    waypoint_actions: Vec<Action>,
+}
+
+impl Solver for RainRisk {
+    fn day(&self) -> u8 {
+        12
+    }
+
+    fn name(&self) -> &'static str {
+        "Rain Risk"
+    }
+
+    fn command(&self) -> App<'static> {
+        Solution::into_app()
+    }
+
+    fn invocation(&self, matches: &ArgMatches) -> Invocation {
+        Solution::from_arg_matches(matches).invocation()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Puzzle>> {
+        let parse_actions = |enable_waypoint| -> Result<Vec<Action>> {
+            input.lines()
+                .enumerate()
+                .map(|(index, line)| {
+                    Action::parse(line, enable_waypoint).map_err(|err| Error::parse(index + 1, err))
+                })
+                .collect()
+        };
+        Ok(Box::new(NavigationInstructions {
+            actions: parse_actions(false)?,
+            waypoint_actions: parse_actions(true)?,
+        }))
+    }
+}
+
+impl Puzzle for NavigationInstructions {
+    fn solve(&self, part: Part) -> Result<Answer> {
+        let mut ship = Ship::new();
+        let actions = match part {
+            Part::One => &self.actions,
+            Part::Two => {
+                ship.act(Action::MoveWaypointEast(9));
+                ship.act(Action::MoveWaypointNorth(1));
+                &self.waypoint_actions
+            }
+        };
+        for action in actions {
+            ship.act(*action);
+        }
+        let distance = part1::manhattan_distance(ship.position());
+        Ok(Answer::new(distance, format!("The manhattan distance is {}.", distance)))
+    }
+}
+
 #[derive(Clap)]
+#[clap(name = "day12", about = "Day 12: Rain Risk")]
 pub struct Solution {
-    input: std::path::PathBuf,
+    #[clap(flatten)]
+    input: InputArgs,
+    #[clap(flatten)]
+    parts: PartArgs,
     #[clap(
         long,
+        conflicts_with = "part",
         about = "Interpret movement instructions in the input file as manipulating the waypoint \
                 instead of the ship"
     )]
@@ -17,21 +108,20 @@ pub struct Solution {
 }
 
 impl Solution {
-    pub fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let data = std::fs::read_to_string(&self.input)?;
-        let lines: Result<Vec<Action>, anyhow::Error> = data.lines()
-            .map(|line| Action::parse(line, self.enable_waypoint))
-            .collect();
-        let mut ship = Ship::new();
-        if self.enable_waypoint {
-            ship.act(Action::MoveWaypointEast(9));
-            ship.act(Action::MoveWaypointNorth(1));
-        }
-        for line in lines? {
-            ship.act(line);
-        }
-        let distance = part1::manhattan_distance(ship.position());
-        println!("The manhattan distance is {}.", distance);
-        Ok(())
+    pub fn invocation(self) -> Invocation {
+        let parts = if self.enable_waypoint { vec![Part::Two] } else { self.parts.parts(&[Part::One]) };
+        Invocation::new(12, self.input.source(12), &parts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reports_the_line_with_an_invalid_action() {
+        let expected_message = "parse error at line 2: error parsing actions: unexpected action type";
+        let result = RainRisk.parse("F10\nQ3\nF7");
+        assert_eq!(result.err().unwrap().to_string(), expected_message);
     }
 }
@@ -1,5 +1,6 @@
 use anyhow::{anyhow, Result};
 use nalgebra::{Matrix3, Vector3};
+use tracing::trace;
 
 pub struct Ship {
     position: (i32, i32),
@@ -7,7 +8,7 @@ pub struct Ship {
     waypoint: (f64, f64),
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub enum Action {
     MoveNorth(u16),
     MoveSouth(u16),
@@ -45,6 +46,10 @@ impl Ship {
         self.position
     }
 
+    pub fn waypoint(&self) -> (f64, f64) {
+        self.waypoint
+    }
+
     fn rotate(&mut self, angle: u16, orientation: Orientation) {
         let angle = match orientation {
             Orientation::Right => 360 - (angle % 360),
@@ -87,6 +92,12 @@ impl Ship {
     }
 }
 
+impl Default for Ship {
+    fn default() -> Self {
+        Ship::new()
+    }
+}
+
 enum Orientation {
     Left, Right
 }
@@ -112,13 +123,17 @@ impl Action {
             Self::RotateWaypointRight(theta) =>
                 ship.rotate_waypoint(*theta, Orientation::Right),
         }
+        trace!(action = ?self, position = ?ship.position, rotation = ship.rotation, waypoint = ?ship.waypoint, "applied action");
     }
 
     pub fn parse(s: impl AsRef<str>, enable_waypoint: bool) -> Result<Action> {
         let s = s.as_ref();
-        let num = s[1..].parse::<u16>()?;
+        let (action, num) = match (s.get(..1), s.get(1..)) {
+            (Some(action), Some(num)) => (action, num.parse::<u16>()?),
+            _ => Err(anyhow!("error parsing actions: missing action type"))?,
+        };
         if enable_waypoint {
-            match &s[0..1] {
+            match action {
                 "N" => Ok(Self::MoveWaypointNorth(num)),
                 "S" => Ok(Self::MoveWaypointSouth(num)),
                 "E" => Ok(Self::MoveWaypointEast(num)),
@@ -129,7 +144,7 @@ impl Action {
                 _ => Err(anyhow!("error parsing actions: unexpected action type"))
             }
         } else {
-            match &s[0..1] {
+            match action {
                 "N" => Ok(Self::MoveNorth(num)),
                 "S" => Ok(Self::MoveSouth(num)),
                 "E" => Ok(Self::MoveEast(num)),
@@ -1,67 +1,158 @@
-mod departure_finder;
-mod multiple_finder;
+pub mod departure_finder;
+pub mod multiple_finder;
 
-use anyhow::{
-    anyhow,
-    Result,
+use clap::{App, ArgMatches, Clap, FromArgMatches, IntoApp};
+
+use super::{
+    solver::{Answer, Example, Part, Puzzle, Solver},
+    Invocation, PartArgs,
 };
-use clap::Clap;
-use std::{
-    error::Error,
-    fs::File,
-    io::{
-        BufRead,
-        BufReader,
-    }
+use crate::{
+    error::{Error, Result},
+    input::InputArgs,
 };
-
 use departure_finder::earliest_departure;
 use multiple_finder::NextMultiple;
 
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: Part::One,
+        input: include_str!("day13/example.txt"),
+        expected: "295",
+    },
+    Example {
+        part: Part::Two,
+        input: include_str!("day13/example.txt"),
+        expected: "1068781",
+    },
+];
+
+pub struct ShuttleSearch;
+
+struct Schedule {
+    earliest_departure: u64,
+    buses: Vec<(usize, u64)>,
+}
+
+impl Solver for ShuttleSearch {
+    fn day(&self) -> u8 {
+        13
+    }
+
+    fn name(&self) -> &'static str {
+        "Shuttle Search"
+    }
+
+    fn command(&self) -> App<'static> {
+        Solution::into_app()
+    }
+
+    fn invocation(&self, matches: &ArgMatches) -> Invocation {
+        Solution::from_arg_matches(matches).invocation()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Puzzle>> {
+        Ok(Box::new(Schedule::parse(input)?))
+    }
+}
+
+impl Puzzle for Schedule {
+    fn solve(&self, part: Part) -> Result<Answer> {
+        match part {
+            Part::One => {
+                let (next_bus, departure_time) = self.buses.iter()
+                    .map(|(_, x)| (x, self.earliest_departure.next_multiple(*x)))
+                    .min_by(|(_, lhs), (_, rhs)| lhs.cmp(rhs))
+                    .ok_or_else(|| Error::unsolvable("the schedule has no buses in service"))?;
+                let waiting_time = departure_time - self.earliest_departure;
+                Ok(Answer::new(
+                    waiting_time * next_bus,
+                    format!(
+                        "You have to wait {} minutes for bus {} to arrive. The multiplier is {}.",
+                        waiting_time,
+                        next_bus,
+                        waiting_time * next_bus
+                    ),
+                ))
+            }
+            Part::Two => {
+                let (offsets, buses): (Vec<_>, Vec<_>) = self.buses.iter().cloned().unzip();
+                let winning_timestamp = earliest_departure(
+                    buses.as_slice(),
+                    offsets.as_slice()
+                ).ok_or_else(|| Error::unsolvable("no winning timestamp exists for the schedule"))?;
+                Ok(Answer::new(
+                    winning_timestamp,
+                    format!("The winning timestamp is: {}", winning_timestamp),
+                ))
+            }
+        }
+    }
+}
+
+impl Schedule {
+    fn parse(input: &str) -> Result<Schedule> {
+        let mut lines = input.lines();
+        let my_time = lines.next()
+            .ok_or_else(|| Error::parse(1, "input missing first line"))?
+            .parse()
+            .map_err(|err| Error::parse(1, err))?;
+        let schedule = lines.next()
+            .ok_or_else(|| Error::parse(2, "input missing schedule of times"))?;
+        let mut buses = Vec::new();
+        let mut column = 1;
+        for (index, bus) in schedule.split(',').enumerate() {
+            if bus != "x" {
+                match bus.parse() {
+                    Ok(0) => Err(Error::parse_at(2, column, "bus IDs must be positive"))?,
+                    Ok(bus) => buses.push((index, bus)),
+                    Err(err) => Err(Error::parse_at(2, column, err))?,
+                }
+            }
+            column += bus.len() + 1;
+        }
+        Ok(Schedule {
+            earliest_departure: my_time,
+            buses,
+        })
+    }
+}
+
 #[derive(Clap)]
+#[clap(name = "day13", about = "Day 13: Shuttle Search")]
 pub struct Solution {
-    input: std::path::PathBuf,
+    #[clap(flatten)]
+    input: InputArgs,
+    #[clap(flatten)]
+    parts: PartArgs,
 }
 
 impl Solution {
-    pub fn run(&self) -> Result<(), Box<dyn Error>> {
-        let reader = BufReader::new(File::open(&self.input)?);
-        let (earliest_depature, buses) = Solution::parse(reader)?;
-        let (next_bus, departure_time) = buses.iter()
-            .map(|(_, x)| (x, earliest_depature.next_multiple(*x)))
-            .min_by(|(_, lhs), (_, rhs)| lhs.cmp(rhs))
-            .ok_or(anyhow!("no minimum number was found, which is unexpected"))?;
-        let waiting_time = departure_time - earliest_depature;
-        println!(
-            "You have to wait {} minutes for bus {} to arrive. The multiplier is {}.",
-            waiting_time,
-            next_bus,
-            waiting_time * next_bus
-        );
-        let (offsets, buses): (Vec<_>, Vec<_>) = buses.iter().cloned().unzip();
-        if let Some(winning_timestamp) = earliest_departure(
-            buses.as_slice(),
-            offsets.as_slice()
-        ) {
-            println!("The winning timestamp is: {}", winning_timestamp);
-        }
-        Ok(())
+    pub fn invocation(self) -> Invocation {
+        Invocation::new(13, self.input.source(13), &self.parts.parts(&[Part::One, Part::Two]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reports_the_column_of_an_invalid_bus() {
+        let expected_message = "parse error at line 2, column 6: invalid digit found in string";
+        let result = ShuttleSearch.parse("939\n7,13,q,x,59");
+        assert_eq!(result.err().unwrap().to_string(), expected_message);
     }
 
-    fn parse(file: impl BufRead) -> Result<(u64, Vec<(usize, u64)>)> {
-        let mut lines = file.lines();
-        let my_time = lines.next()
-            .ok_or(anyhow!("input missing first line"))??
-            .parse()?;
-        let times: Result<Vec<_>, _> = lines.next()
-            .ok_or(anyhow!("input missing schedule of times"))??
-            .split(",")
-            .enumerate()
-            .filter(|(_, x)| *x != "x")
-            .map(|(index, x)| {
-                x.parse().and_then(|x| Ok((index, x)))
-            })
-            .collect();
-        Ok((my_time, times?))
+    #[test]
+    fn a_schedule_without_buses_is_unsolvable() -> Result<()> {
+        let expected_message = "the input is unsolvable: the schedule has no buses in service";
+        let schedule = ShuttleSearch.parse("939\nx,x")?;
+        let result = schedule.solve(Part::One);
+        Ok(assert_eq!(result.err().unwrap().to_string(), expected_message))
     }
 }
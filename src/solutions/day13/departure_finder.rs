@@ -1,15 +1,17 @@
 use num::Integer;
 use std::iter::Iterator;
+use tracing::debug;
 
 pub fn earliest_departure(buses: &[u64], offsets: &[usize]) -> Option<u64> {
-    if buses.len() == offsets.len() && buses.len() > 0 {
+    if buses.len() == offsets.len() && !buses.is_empty() {
         let mut result = buses[0];
         let mut increment = buses[0];
         for (bus, offset) in buses.iter().zip(offsets.iter()).skip(1) {
-            while (result + *offset as u64) % bus != 0 {
+            while !(result + *offset as u64).is_multiple_of(*bus) {
                 result += increment;
             }
             increment = increment.lcm(bus);
+            debug!(bus, offset, timestamp = result, increment, "aligned bus");
         }
         Some(result)
     } else {
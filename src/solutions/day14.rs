@@ -1,24 +1,183 @@
-mod comporter;
+pub mod comporter;
 
-use anyhow::Result;
-use clap::Clap;
+use super::{
+    solver::{Answer, Example, Part, Puzzle, Solver},
+    Invocation, PartArgs,
+};
+use crate::{
+    error::{Error, Result},
+    input::InputArgs,
+    progress::Progress,
+};
+use clap::{App, ArgMatches, Clap, FromArgMatches, IntoApp};
+use comporter::{Bit, Comporter, ProgramStatement, Version};
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: Part::One,
+        input: include_str!("day14/example_part1.txt"),
+        expected: "165",
+    },
+    Example {
+        part: Part::Two,
+        input: include_str!("day14/example_part2.txt"),
+        expected: "208",
+    },
+];
+
+pub const MAX_FLOATING_BITS: usize = 20;
+
+pub struct DockingData;
+
+struct Program(Vec<ProgramStatement>);
+
+impl Solver for DockingData {
+    fn day(&self) -> u8 {
+        14
+    }
+
+    fn name(&self) -> &'static str {
+        "Docking Data"
+    }
+
+    fn command(&self) -> App<'static> {
+        Solution::into_app()
+    }
+
+    fn invocation(&self, matches: &ArgMatches) -> Invocation {
+        Solution::from_arg_matches(matches).invocation()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Puzzle>> {
+        let program: Result<Vec<_>> = input.lines()
+            .enumerate()
+            .map(|(index, line)| {
+                ProgramStatement::parse(line).map_err(|err| Error::parse(index + 1, err))
+            })
+            .collect();
+        Ok(Box::new(Program(program?)))
+    }
+}
+
+impl Puzzle for Program {
+    fn solve(&self, part: Part) -> Result<Answer> {
+        self.solve_with(part, &Progress::new())
+    }
+
+    fn solve_with(&self, part: Part, progress: &Progress) -> Result<Answer> {
+        let version = match part {
+            Part::One => Version::One,
+            Part::Two => {
+                self.check_floating_bits()?;
+                Version::Two
+            }
+        };
+        let mut compy = Comporter::new(version);
+        progress.start(self.memory_writes(part), "memory writes");
+        compy.run_with_progress(&self.0, progress)?;
+        let sum = compy.sum_of_memory();
+        Ok(Answer::new(sum, format!("The sum of all values in memory is {}.", sum)))
+    }
+}
+
+impl Program {
+    fn memory_writes(&self, part: Part) -> u64 {
+        let mut floating_bits = 0;
+        let mut writes = 0;
+        for statement in &self.0 {
+            match statement {
+                ProgramStatement::Mask(mask) => floating_bits = mask.iter().filter(|bit| **bit == Bit::Any).count(),
+                ProgramStatement::Instruction(..) => writes += match part {
+                    Part::One => 1,
+                    Part::Two => 1 << floating_bits,
+                },
+            }
+        }
+        writes
+    }
+
+    fn check_floating_bits(&self) -> Result<()> {
+        for statement in &self.0 {
+            if let ProgramStatement::Mask(mask) = statement {
+                let floating_bits = mask.iter().filter(|bit| **bit == Bit::Any).count();
+                if floating_bits > MAX_FLOATING_BITS {
+                    Err(Error::resource_limit(format!(
+                        "mask {} has {} floating bits (at most {} are supported)",
+                        mask,
+                        floating_bits,
+                        MAX_FLOATING_BITS,
+                    )))?
+                }
+            }
+        }
+        Ok(())
+    }
+}
 
 #[derive(Clap)]
+#[clap(name = "day14", about = "Day 14: Docking Data")]
 pub struct Solution {
-    input: std::path::PathBuf,
-    #[clap(arg_enum, default_value = "one", long = "--comporter-version", about = "Which version of the comporter to use")]
-    comporter_version: comporter::Version,
+    #[clap(flatten)]
+    input: InputArgs,
+    #[clap(flatten)]
+    parts: PartArgs,
+    #[clap(
+        arg_enum,
+        long = "--comporter-version",
+        conflicts_with = "part",
+        about = "Which version of the comporter to use"
+    )]
+    comporter_version: Option<comporter::Version>,
 }
 
 impl Solution {
-    pub fn run(self) -> Result<(), Box<dyn std::error::Error>> {
-        let program = std::fs::File::open(&self.input)?;
-        let mut compy = comporter::Comporter::new(self.comporter_version);
-        compy.exec(program)?;
-        println!(
-            "The sum of all values in memory is {}.",
-            compy.sum_of_memory()
-        );
-        Ok(())
+    pub fn invocation(self) -> Invocation {
+        let parts = match self.comporter_version {
+            Some(Version::One) => vec![Part::One],
+            Some(Version::Two) => vec![Part::Two],
+            None => self.parts.parts(&[Part::One]),
+        };
+        Invocation::new(14, self.input.source(14), &parts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reports_the_line_with_an_invalid_statement() {
+        let expected_message = "parse error at line 2: invalid mask length (got 4 instead of 36)";
+        let result = DockingData.parse("mem[8] = 11\nmask = XX10\nmem[7] = 101");
+        assert_eq!(result.err().unwrap().to_string(), expected_message);
+    }
+
+    #[test]
+    fn it_refuses_to_expand_too_many_floating_bits() -> Result<()> {
+        let program = DockingData.parse("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX\nmem[8] = 11")?;
+        let result = program.solve(Part::Two);
+        Ok(assert_eq!(result.err().unwrap().kind(), "resource_limit"))
+    }
+
+    #[test]
+    fn it_counts_progress_in_memory_writes() -> Result<()> {
+        let program = DockingData.parse(EXAMPLES[1].input)?;
+        let progress = Progress::new();
+        program.solve_with(Part::Two, &progress)?;
+        assert_eq!(progress.total(), 12);
+        Ok(assert_eq!(progress.done(), 12))
+    }
+
+    #[test]
+    fn it_stops_expanding_floating_addresses_once_cancelled() -> Result<()> {
+        let program = DockingData.parse("mask = XXXXXXXXXXXXXXXXXXXX0000000000000000\nmem[8] = 11")?;
+        let progress = Progress::new();
+        progress.cancel();
+        let result = program.solve_with(Part::Two, &progress);
+        Ok(assert_eq!(result.err().unwrap().kind(), "cancelled"))
     }
 }
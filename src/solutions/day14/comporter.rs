@@ -4,16 +4,18 @@ mod program_statement;
 mod value_decoder;
 mod version;
 
+pub use mask::{Bit, Mask, MASK_LEN};
+pub use program_statement::ProgramStatement;
 pub use version::Version;
 
+use crate::{error, progress::Progress};
 use address_decoder::AddressDecoder;
 use anyhow::Result;
-use mask::Mask;
-use program_statement::ProgramStatement;
 use std::{
     collections::HashMap,
     io::{BufRead, BufReader, Read},
 };
+use tracing::trace;
 use value_decoder::ValueDecoder;
 
 pub struct Comporter {
@@ -29,19 +31,42 @@ impl Comporter {
         Comporter {
             mask: Mask::new(),
             memory: HashMap::new(),
-            address_decoder: address_decoder,
-            value_decoder: value_decoder,
+            address_decoder,
+            value_decoder,
         }
     }
 
-    pub fn exec<'a>(&mut self, src: impl Read) -> Result<()> {
+    pub fn exec(&mut self, src: impl Read) -> Result<()> {
         let reader = BufReader::new(src);
         let lines = reader.lines();
         for line in lines {
-            match ProgramStatement::parse(line?.as_ref())? {
-                ProgramStatement::Instruction(address, value) => self.set_memory(address, value),
-                ProgramStatement::Mask(mask) => self.set_mask(mask)?,
-            }
+            self.step(&ProgramStatement::parse(line?.as_ref())?);
+        }
+        Ok(())
+    }
+
+    pub fn run(&mut self, program: &[ProgramStatement]) {
+        for statement in program {
+            self.step(statement);
+        }
+    }
+
+    pub fn run_with_progress(&mut self, program: &[ProgramStatement], progress: &Progress) -> error::Result<()> {
+        for statement in program {
+            self.step_with_progress(statement, progress)?;
+        }
+        Ok(())
+    }
+
+    pub fn step(&mut self, statement: &ProgramStatement) {
+        self.step_with_progress(statement, &Progress::new())
+            .expect("a step only stops early when cancelled")
+    }
+
+    pub fn step_with_progress(&mut self, statement: &ProgramStatement, progress: &Progress) -> error::Result<()> {
+        match statement {
+            ProgramStatement::Instruction(address, value) => self.set_memory_with_progress(*address, *value, progress)?,
+            ProgramStatement::Mask(mask) => self.mask = mask.clone(),
         }
         Ok(())
     }
@@ -52,10 +77,21 @@ impl Comporter {
     }
 
     pub fn set_memory(&mut self, index: usize, value: u64) {
+        self.set_memory_with_progress(index, value, &Progress::new())
+            .expect("a memory write only stops early when cancelled")
+    }
+
+    pub fn set_memory_with_progress(&mut self, index: usize, value: u64, progress: &Progress) -> error::Result<()> {
         let memory_value = self.value_decoder.decode(value, &self.mask);
-        for address in self.address_decoder.decode(index, &self.mask) {
+        for address in self.address_decoder.decode_with_progress(index, &self.mask, progress)? {
+            trace!(index, value, address, memory_value, "wrote memory");
             *self.memory.entry(address).or_insert(memory_value) = memory_value;
         }
+        Ok(())
+    }
+
+    pub fn memory(&self, address: usize) -> Option<u64> {
+        self.memory.get(&address).copied()
     }
 
     pub fn sum_of_memory(&self) -> u64 {
@@ -2,10 +2,16 @@ mod floating_decoder;
 mod null_decoder;
 
 use super::mask::Mask;
+use crate::{error::Result, progress::Progress};
 
 pub use floating_decoder::FloatingDecoder;
 pub use null_decoder::NullDecoder;
 
 pub trait AddressDecoder {
-    fn decode(&self, target: usize, mask: &Mask) -> Vec<usize>;
+    fn decode_with_progress(&self, target: usize, mask: &Mask, progress: &Progress) -> Result<Vec<usize>>;
+
+    fn decode(&self, target: usize, mask: &Mask) -> Vec<usize> {
+        self.decode_with_progress(target, mask, &Progress::new())
+            .expect("decoding only stops early when cancelled")
+    }
 }
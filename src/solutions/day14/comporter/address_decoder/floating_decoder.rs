@@ -1,6 +1,6 @@
 use super::super::mask::{Bit, Mask, MASK_LEN};
 use super::AddressDecoder;
-use anyhow::Result;
+use crate::{error::Result, progress::Progress};
 use bitvec::prelude::*;
 use std::convert::{TryFrom, TryInto};
 
@@ -13,7 +13,7 @@ impl FloatingDecoder {
 }
 
 impl AddressDecoder for FloatingDecoder {
-    fn decode(&self, address: usize, mask: &Mask) -> Vec<usize> {
+    fn decode_with_progress(&self, address: usize, mask: &Mask, progress: &Progress) -> Result<Vec<usize>> {
         let address_bits = address.view_bits::<Msb0>();
         let combined_mask: Vec<Bit> = address_bits
             .into_iter()
@@ -31,33 +31,40 @@ impl AddressDecoder for FloatingDecoder {
             })
             .collect();
         let combined_mask: Mask = combined_mask.try_into().expect("valid mask");
-        masks_for_floating_bits(&combined_mask)
+        Ok(masks_for_floating_bits(&combined_mask, progress)?
             .into_iter()
             .map(|m| m.try_into().expect("valid u64"))
-            .collect()
+            .collect())
     }
 }
 
-fn masks_for_floating_bits(mask: &Mask) -> Vec<Mask> {
-    fn masks_for_floating_bits_rec(bits: &[Bit], offset: usize, result: &mut Vec<Vec<Bit>>) {
+fn masks_for_floating_bits(mask: &Mask, progress: &Progress) -> Result<Vec<Mask>> {
+    fn masks_for_floating_bits_rec(
+        bits: &[Bit],
+        offset: usize,
+        progress: &Progress,
+        result: &mut Vec<Vec<Bit>>,
+    ) -> Result<()> {
         if offset == bits.len() {
-            result.push(bits.to_vec())
+            progress.advance(1)?;
+            result.push(bits.to_vec());
+            Ok(())
         } else if bits[offset] == Bit::Any {
             let mut one_mask = bits.to_vec();
             one_mask[offset] = Bit::One;
             let mut zero_mask = bits.to_vec();
             zero_mask[offset] = Bit::Zero;
-            masks_for_floating_bits_rec(one_mask.as_slice(), offset + 1, result);
-            masks_for_floating_bits_rec(zero_mask.as_slice(), offset + 1, result);
+            masks_for_floating_bits_rec(one_mask.as_slice(), offset + 1, progress, result)?;
+            masks_for_floating_bits_rec(zero_mask.as_slice(), offset + 1, progress, result)
         } else {
-            masks_for_floating_bits_rec(bits, offset + 1, result)
+            masks_for_floating_bits_rec(bits, offset + 1, progress, result)
         }
     }
     let mask_vec: Vec<Bit> = mask.iter().copied().collect();
     let mut masks = Vec::new();
-    masks_for_floating_bits_rec(mask_vec.as_slice(), 0, &mut masks);
-    let masks: Result<Vec<Mask>> = masks.into_iter().map(|v| Mask::try_from(v)).collect();
-    masks.expect("valid bit mask")
+    masks_for_floating_bits_rec(mask_vec.as_slice(), 0, progress, &mut masks)?;
+    let masks: anyhow::Result<Vec<Mask>> = masks.into_iter().map(Mask::try_from).collect();
+    Ok(masks.expect("valid bit mask"))
 }
 
 #[cfg(test)]
@@ -65,7 +72,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn generates_all_addresses_for_the_floating_bits() -> Result<()> {
+    fn generates_all_addresses_for_the_floating_bits() -> anyhow::Result<()> {
         let mut expected_addresses = vec![
             0b000000000000000000000000000000011010,
             0b000000000000000000000000000000011011,
@@ -80,4 +87,23 @@ mod tests {
         result.sort();
         Ok(assert_eq!(result, expected_addresses))
     }
+
+    #[test]
+    fn it_counts_each_address_it_expands() -> anyhow::Result<()> {
+        let mask = Mask::parse("000000000000000000000000000000X1001X")?;
+        let progress = Progress::new();
+        FloatingDecoder::new().decode_with_progress(42, &mask, &progress)?;
+        Ok(assert_eq!(progress.done(), 4))
+    }
+
+    #[test]
+    fn it_stops_expanding_once_cancelled() -> anyhow::Result<()> {
+        let mask = Mask::parse("XXXXXXXXXXXXXXXXXXXX0000000000000000")?;
+        let progress = Progress::new();
+        progress.start(1 << 20, "memory writes");
+        progress.cancel();
+        let result = FloatingDecoder::new().decode_with_progress(0, &mask, &progress);
+        assert_eq!(progress.done(), 1);
+        Ok(assert_eq!(result.err().map(|err| err.kind()), Some("cancelled")))
+    }
 }
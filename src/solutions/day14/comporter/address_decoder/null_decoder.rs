@@ -1,4 +1,5 @@
 use super::{super::mask::Mask, AddressDecoder};
+use crate::{error::Result, progress::Progress};
 
 pub struct NullDecoder {}
 
@@ -9,8 +10,9 @@ impl NullDecoder {
 }
 
 impl AddressDecoder for NullDecoder {
-    fn decode(&self, target: usize, _mask: &Mask) -> Vec<usize> {
-        vec![target]
+    fn decode_with_progress(&self, target: usize, _mask: &Mask, progress: &Progress) -> Result<Vec<usize>> {
+        progress.advance(1)?;
+        Ok(vec![target])
     }
 }
 
@@ -3,11 +3,11 @@ use std::convert::{TryFrom, TryInto};
 
 pub const MASK_LEN: usize = 36;
 
-const ZERO_BIT: u8 = '0' as u8;
-const ONE_BIT: u8 = '1' as u8;
-const ANY_BIT: u8 = 'X' as u8;
+const ZERO_BIT: u8 = b'0';
+const ONE_BIT: u8 = b'1';
+const ANY_BIT: u8 = b'X';
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Mask {
     raw_mask: [Bit; 36],
 }
@@ -58,6 +58,12 @@ impl Mask {
     }
 }
 
+impl Default for Mask {
+    fn default() -> Self {
+        Mask::new()
+    }
+}
+
 impl std::fmt::Display for Mask {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for bit in self.raw_mask.iter() {
@@ -1,3 +1,4 @@
+use super::mask::Mask;
 use anyhow::{anyhow, Result};
 use nom::{
     branch::alt,
@@ -8,13 +9,14 @@ use nom::{
     Finish,
 };
 
-pub enum ProgramStatement<'a> {
+#[derive(Clone, Debug)]
+pub enum ProgramStatement {
     Instruction(usize, u64),
-    Mask(&'a str),
+    Mask(Mask),
 }
 
-impl<'a> ProgramStatement<'a> {
-    pub fn parse(line: &'a str) -> Result<ProgramStatement> {
+impl ProgramStatement {
+    pub fn parse(line: &str) -> Result<ProgramStatement> {
         fn mask_statement<'a>() -> impl FnMut(&'a str) -> nom::IResult<&'a str, &'a str> {
             let mask_def = tag("mask");
             let mask_content = take_while1(|s| s == 'X' || s == '1' || s == '0');
@@ -43,11 +45,20 @@ impl<'a> ProgramStatement<'a> {
         }
         let mut parser = alt((
             map(mem_statement(), |(addr, value)| {
-                ProgramStatement::Instruction(addr, value)
+                Ok(ProgramStatement::Instruction(addr, value))
             }),
-            map(mask_statement(), ProgramStatement::Mask),
+            map(mask_statement(), |mask| Mask::parse(mask).map(ProgramStatement::Mask)),
         ));
         let (_, result) = parser(line).finish().map_err(|e| anyhow!("{}", e))?;
-        Ok(result)
+        result
+    }
+}
+
+impl std::fmt::Display for ProgramStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProgramStatement::Instruction(address, value) => write!(f, "mem[{}] = {}", address, value),
+            ProgramStatement::Mask(mask) => write!(f, "mask = {}", mask),
+        }
     }
 }
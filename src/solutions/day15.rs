@@ -1,29 +1,156 @@
-mod counting_game;
+pub mod counting_game;
 
-use anyhow::anyhow;
-use clap::Clap;
-use std::error::Error;
+use super::{
+    solver::{Answer, Example, Part, Puzzle, Solver},
+    Invocation, PartArgs,
+};
+use crate::{
+    error::{Error, Result},
+    input::InputArgs,
+    progress::Progress,
+};
+use clap::{App, ArgMatches, Clap, FromArgMatches, IntoApp};
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: Part::One,
+        input: include_str!("day15/example.txt"),
+        expected: "436",
+    },
+    Example {
+        part: Part::Two,
+        input: include_str!("day15/example.txt"),
+        expected: "175594",
+    },
+];
+
+const MAX_TURNS: usize = i32::MAX as usize;
+const PROGRESS_INTERVAL: usize = 100_000;
+
+#[derive(Default)]
+pub struct RambunctiousRecitation {
+    nth: Option<usize>,
+}
+
+struct StartingNumbers {
+    numbers: Vec<i32>,
+    nth: Option<usize>,
+}
+
+impl RambunctiousRecitation {
+    pub fn new() -> RambunctiousRecitation {
+        RambunctiousRecitation::default()
+    }
+
+    pub fn with_nth(nth: usize) -> RambunctiousRecitation {
+        RambunctiousRecitation { nth: Some(nth) }
+    }
+}
+
+impl Solver for RambunctiousRecitation {
+    fn day(&self) -> u8 {
+        15
+    }
+
+    fn name(&self) -> &'static str {
+        "Rambunctious Recitation"
+    }
+
+    fn command(&self) -> App<'static> {
+        Solution::into_app()
+    }
+
+    fn invocation(&self, matches: &ArgMatches) -> Invocation {
+        Solution::from_arg_matches(matches).invocation()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Puzzle>> {
+        let line = input
+            .lines()
+            .next()
+            .filter(|line| !line.trim().is_empty())
+            .ok_or_else(|| Error::parse(1, "expected a list of starting numbers"))?;
+        let mut numbers = Vec::new();
+        let mut column = 1;
+        for number in line.split(',') {
+            numbers.push(number.parse().map_err(|err| Error::parse_at(1, column, err))?);
+            column += number.len() + 1;
+        }
+        Ok(Box::new(StartingNumbers {
+            numbers,
+            nth: self.nth,
+        }))
+    }
+}
+
+impl Puzzle for StartingNumbers {
+    fn solve(&self, part: Part) -> Result<Answer> {
+        self.solve_with(part, &Progress::new())
+    }
+
+    fn solve_with(&self, part: Part, progress: &Progress) -> Result<Answer> {
+        let nth = self.nth.unwrap_or(match part {
+            Part::One => 2020,
+            Part::Two => 30_000_000,
+        });
+        if nth == 0 {
+            Err(Error::unsolvable("the game starts at turn 1"))?
+        }
+        if nth > MAX_TURNS {
+            Err(Error::resource_limit(format!("the game can be played for at most {} turns", MAX_TURNS)))?
+        }
+        progress.start(nth as u64, "turns");
+        let mut game = counting_game::counting_game_iter(&self.numbers);
+        for turn in (0 .. nth - 1).step_by(PROGRESS_INTERVAL) {
+            progress.update(turn as u64)?;
+            game.by_ref().take(PROGRESS_INTERVAL.min(nth - 1 - turn)).for_each(drop);
+        }
+        let target_number = game.next()
+            .ok_or_else(|| Error::unsolvable("something has gone terribly wrong. :("))?;
+        Ok(Answer::new(target_number, format!("#{} in the game is {}.", nth, target_number)))
+    }
+}
 
 #[derive(Clap)]
+#[clap(name = "day15", about = "Day 15: Rambunctious Recitation")]
 pub struct Solution {
-    input: std::path::PathBuf,
-    #[clap(default_value = "2020")]
-    nth: usize,
+    #[clap(flatten)]
+    input: InputArgs,
+    #[clap(flatten)]
+    parts: PartArgs,
+    #[clap(long, conflicts_with = "part", about = "Which number spoken in the game to find instead of the one for the part")]
+    nth: Option<usize>,
 }
 
 impl Solution {
-    pub fn run(self) -> Result<(), Box<dyn Error>> {
-        let input: Result<Vec<i32>, _> = std::fs::read_to_string(self.input)?
-            .strip_suffix("\n")
-            .ok_or(anyhow!("invalid input format"))?
-            .split(",")
-            .map(|x| x.parse())
-            .collect();
-        let mut sequence = counting_game::counting_game_iter(&input?);
-        let target_number = sequence
-            .nth(self.nth - 1)
-            .ok_or(anyhow!("something has gone terribly wrong. :("))?;
-        println!("#{} in the game is {}.", self.nth, target_number);
-        Ok(())
+    pub fn invocation(self) -> Invocation {
+        match self.nth {
+            Some(nth) => Invocation::new(15, self.input.source(15), &[Part::One])
+                .with_solver(RambunctiousRecitation::with_nth(nth))
+                .unchecked(),
+            None => Invocation::new(15, self.input.source(15), &self.parts.parts(&[Part::One])),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_accepts_input_without_a_trailing_newline() -> Result<()> {
+        let answer = RambunctiousRecitation::new().run("0,3,6", Part::One)?;
+        Ok(assert_eq!(answer.value.to_string(), "436"))
+    }
+
+    #[test]
+    fn it_rejects_empty_input() {
+        let expected_message = "parse error at line 1: expected a list of starting numbers";
+        let result = RambunctiousRecitation::new().parse("\n");
+        assert_eq!(result.err().unwrap().to_string(), expected_message)
     }
 }
@@ -1,4 +1,7 @@
 use std::{collections::HashMap, iter::from_fn};
+use tracing::debug;
+
+const PROGRESS_INTERVAL: i32 = 1_000_000;
 
 pub fn counting_game_iter(input: &[i32]) -> impl Iterator<Item = i32> {
     let mut initial_numbers = input.to_vec();
@@ -21,6 +24,9 @@ pub fn counting_game_iter(input: &[i32]) -> impl Iterator<Item = i32> {
             .and_modify(|e| *e = (turn_number, e.0))
             .or_insert((turn_number, turn_number));
         turn_number += 1;
+        if turn_number % PROGRESS_INTERVAL == 0 {
+            debug!(turn = turn_number, distinct_numbers = game_state.len(), "counting game progress");
+        }
         Some(last_number)
     })
 }
@@ -0,0 +1,160 @@
+pub mod password_entry;
+pub mod policy;
+
+use super::{
+    solver::{Answer, Example, Part, Puzzle, Solver},
+    Invocation, PartArgs,
+};
+use crate::{
+    error::{Error, Result},
+    input::InputArgs,
+};
+use clap::{App, ArgMatches, Clap, FromArgMatches, IntoApp};
+use password_entry::PasswordEntry;
+use policy::Policy;
+use std::fmt::Write;
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: Part::One,
+        input: include_str!("day2/example.txt"),
+        expected: "2",
+    },
+    Example {
+        part: Part::Two,
+        input: include_str!("day2/example.txt"),
+        expected: "1",
+    },
+];
+
+#[derive(Default)]
+pub struct PasswordPhilosophy {
+    show_invalid: bool,
+}
+
+struct PasswordDatabase {
+    entries: Vec<PasswordEntry>,
+    show_invalid: bool,
+}
+
+impl PasswordPhilosophy {
+    pub fn new() -> PasswordPhilosophy {
+        PasswordPhilosophy::default()
+    }
+
+    pub fn showing_invalid() -> PasswordPhilosophy {
+        PasswordPhilosophy { show_invalid: true }
+    }
+}
+
+impl Solver for PasswordPhilosophy {
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        "Password Philosophy"
+    }
+
+    fn command(&self) -> App<'static> {
+        Solution::into_app()
+    }
+
+    fn invocation(&self, matches: &ArgMatches) -> Invocation {
+        Solution::from_arg_matches(matches).invocation()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Puzzle>> {
+        let entries = input.lines()
+            .enumerate()
+            .map(|(index, line)| PasswordEntry::parse(line).map_err(|err| Error::parse(index + 1, err)))
+            .collect::<Result<_>>()?;
+        Ok(Box::new(PasswordDatabase {
+            entries,
+            show_invalid: self.show_invalid,
+        }))
+    }
+}
+
+impl Puzzle for PasswordDatabase {
+    fn solve(&self, part: Part) -> Result<Answer> {
+        let policy = match part {
+            Part::One => Policy::Count,
+            Part::Two => Policy::Position,
+        };
+        let invalid: Vec<(usize, &PasswordEntry)> = self.entries.iter()
+            .enumerate()
+            .filter(|(_, entry)| !entry.is_valid(policy))
+            .map(|(index, entry)| (index + 1, entry))
+            .collect();
+        let valid = self.entries.len() - invalid.len();
+        let mut description = format!(
+            "{} of {} passwords are valid under the {} policy.",
+            valid,
+            self.entries.len(),
+            policy
+        );
+        if self.show_invalid {
+            for (line, entry) in invalid {
+                let _ = write!(description, "\nLine {} fails the {} policy: {}", line, policy, entry);
+            }
+        }
+        Ok(Answer::new(valid, description))
+    }
+}
+
+#[derive(Clap)]
+#[clap(name = "day2", about = "Day 2: Password Philosophy")]
+pub struct Solution {
+    #[clap(flatten)]
+    input: InputArgs,
+    #[clap(flatten)]
+    parts: PartArgs,
+    #[clap(arg_enum, long, conflicts_with = "part", about = "Which policy to check the passwords against")]
+    policy: Option<Policy>,
+    #[clap(long, about = "List the lines whose passwords fail the policy")]
+    show_invalid: bool,
+}
+
+impl Solution {
+    pub fn invocation(self) -> Invocation {
+        let parts = match self.policy {
+            Some(Policy::Count) => vec![Part::One],
+            Some(Policy::Position) => vec![Part::Two],
+            None => self.parts.parts(&[Part::One, Part::Two]),
+        };
+        let invocation = Invocation::new(2, self.input.source(2), &parts);
+        if self.show_invalid {
+            invocation.with_solver(PasswordPhilosophy::showing_invalid())
+        } else {
+            invocation
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_lists_the_lines_that_fail_each_policy() -> Result<()> {
+        let database = PasswordPhilosophy::showing_invalid().parse(EXAMPLES[0].input)?;
+        let expected_count = "2 of 3 passwords are valid under the count policy.\n\
+                              Line 2 fails the count policy: 1-3 b: cdefg";
+        let expected_position = "1 of 3 passwords are valid under the position policy.\n\
+                                 Line 2 fails the position policy: 1-3 b: cdefg\n\
+                                 Line 3 fails the position policy: 2-9 c: ccccccccc";
+        assert_eq!(database.solve(Part::One)?.description, expected_count);
+        Ok(assert_eq!(database.solve(Part::Two)?.description, expected_position))
+    }
+
+    #[test]
+    fn it_reports_the_line_of_an_invalid_entry() {
+        let result = PasswordPhilosophy::new().parse("1-3 a: abcde\n1-3 b cdefg\n");
+        assert!(result.err().unwrap().to_string().starts_with("parse error at line 2: "))
+    }
+}
@@ -0,0 +1,103 @@
+use super::policy::Policy;
+use anyhow::{anyhow, Result};
+use nom::{
+    bytes::complete::take_while1,
+    character::complete::{char, digit1, satisfy, space1},
+    combinator::{eof, map, map_res},
+    sequence::{pair, preceded, terminated, tuple},
+    Finish,
+};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PasswordEntry {
+    pub first: usize,
+    pub second: usize,
+    pub letter: char,
+    pub password: String,
+}
+
+impl PasswordEntry {
+    pub fn parse(line: &str) -> Result<PasswordEntry> {
+        fn number<'a>() -> impl FnMut(&'a str) -> nom::IResult<&'a str, usize> {
+            map_res(digit1, |s: &str| s.parse::<usize>())
+        }
+        let policy = tuple((
+            number(),
+            preceded(char('-'), number()),
+            preceded(space1, satisfy(|c| c.is_ascii_lowercase())),
+        ));
+        let password = preceded(pair(char(':'), space1), take_while1(|c: char| !c.is_whitespace()));
+        let mut parser = terminated(
+            map(pair(policy, password), |((first, second, letter), password)| PasswordEntry {
+                first,
+                second,
+                letter,
+                password: password.to_string(),
+            }),
+            eof,
+        );
+        let (_, entry) = parser(line).finish().map_err(|e| anyhow!("{}", e))?;
+        Ok(entry)
+    }
+
+    pub fn is_valid(&self, policy: Policy) -> bool {
+        match policy {
+            Policy::Count => {
+                let occurrences = self.password.chars().filter(|c| *c == self.letter).count();
+                (self.first ..= self.second).contains(&occurrences)
+            }
+            Policy::Position => {
+                let holds_letter = |position: usize| {
+                    position.checked_sub(1).and_then(|index| self.password.chars().nth(index)) == Some(self.letter)
+                };
+                holds_letter(self.first) != holds_letter(self.second)
+            }
+        }
+    }
+}
+
+impl fmt::Display for PasswordEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{} {}: {}", self.first, self.second, self.letter, self.password)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_the_policy_and_password() -> Result<()> {
+        let expected_entry = PasswordEntry {
+            first: 11,
+            second: 12,
+            letter: 'e',
+            password: "qrst".to_string(),
+        };
+        Ok(assert_eq!(PasswordEntry::parse("11-12   e:  qrst")?, expected_entry))
+    }
+
+    #[test]
+    fn it_rejects_malformed_policies() {
+        for line in &["0-3b: zxcv", "5 -6 c: mnop", "7- 8 c: qrst", "9-10 d; mnop", "867-5301 j:80’ssong"] {
+            assert!(PasswordEntry::parse(line).is_err(), "{} should not parse", line);
+        }
+    }
+
+    #[test]
+    fn the_count_policy_checks_the_number_of_occurrences() -> Result<()> {
+        assert!(PasswordEntry::parse("1-10 a: abacadaeafagaaaa")?.is_valid(Policy::Count));
+        assert!(!PasswordEntry::parse("1-10 q: abacadaeafagaaaa")?.is_valid(Policy::Count));
+        Ok(assert!(!PasswordEntry::parse("1-5 a: abacadaeafagaaaa")?.is_valid(Policy::Count)))
+    }
+
+    #[test]
+    fn the_position_policy_needs_exactly_one_position_to_match() -> Result<()> {
+        assert!(PasswordEntry::parse("1-10 a: abacadaeafagaaaa")?.is_valid(Policy::Position));
+        assert!(PasswordEntry::parse("1-10 f: abacadaeafagaaaa")?.is_valid(Policy::Position));
+        assert!(!PasswordEntry::parse("1-3 a: abacadaeafagaaaa")?.is_valid(Policy::Position));
+        assert!(!PasswordEntry::parse("1-10 z: abacadaeafagaaaa")?.is_valid(Policy::Position));
+        Ok(assert!(PasswordEntry::parse("0-2 b: abc")?.is_valid(Policy::Position)))
+    }
+}
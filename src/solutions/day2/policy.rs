@@ -0,0 +1,17 @@
+use clap::Clap;
+use std::fmt;
+
+#[derive(Clap, Clone, Copy, Debug, PartialEq)]
+pub enum Policy {
+    Count,
+    Position,
+}
+
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Policy::Count => f.pad("count"),
+            Policy::Position => f.pad("position"),
+        }
+    }
+}
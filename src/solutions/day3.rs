@@ -0,0 +1,199 @@
+pub mod slope;
+
+use super::{
+    grid::Grid,
+    solver::{Answer, Example, Part, Puzzle, Solver},
+    Invocation, PartArgs,
+};
+use crate::{
+    error::{Error, Result},
+    input::InputArgs,
+};
+use clap::{App, ArgMatches, Clap, FromArgMatches, IntoApp};
+use slope::Slope;
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: Part::One,
+        input: include_str!("day3/example.txt"),
+        expected: "7",
+    },
+    Example {
+        part: Part::Two,
+        input: include_str!("day3/example.txt"),
+        expected: "336",
+    },
+];
+
+const TREE: u8 = b'#';
+const OPEN: u8 = b'.';
+
+const PART1_SLOPES: &[Slope] = &[Slope { right: 3, down: 1 }];
+const PART2_SLOPES: &[Slope] = &[
+    Slope { right: 1, down: 1 },
+    Slope { right: 3, down: 1 },
+    Slope { right: 5, down: 1 },
+    Slope { right: 7, down: 1 },
+    Slope { right: 1, down: 2 },
+];
+
+#[derive(Default)]
+pub struct TobogganTrajectory {
+    slopes: Option<Vec<Slope>>,
+}
+
+struct Map {
+    grid: Grid,
+    slopes: Option<Vec<Slope>>,
+}
+
+impl TobogganTrajectory {
+    pub fn new() -> TobogganTrajectory {
+        TobogganTrajectory::default()
+    }
+
+    pub fn with_slopes(slopes: Vec<Slope>) -> TobogganTrajectory {
+        TobogganTrajectory { slopes: Some(slopes) }
+    }
+}
+
+impl Solver for TobogganTrajectory {
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn name(&self) -> &'static str {
+        "Toboggan Trajectory"
+    }
+
+    fn command(&self) -> App<'static> {
+        Solution::into_app()
+    }
+
+    fn invocation(&self, matches: &ArgMatches) -> Invocation {
+        Solution::from_arg_matches(matches).invocation()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Puzzle>> {
+        let grid = Grid::parse(input)?;
+        if let Some(index) = grid.cells().iter().position(|cell| *cell != TREE && *cell != OPEN) {
+            Err(Error::parse_at(
+                index / grid.width + 1,
+                index % grid.width + 1,
+                "expected '.' or '#'",
+            ))?
+        }
+        Ok(Box::new(Map {
+            grid,
+            slopes: self.slopes.clone(),
+        }))
+    }
+}
+
+impl Puzzle for Map {
+    fn solve(&self, part: Part) -> Result<Answer> {
+        let slopes = match (&self.slopes, part) {
+            (Some(slopes), _) => slopes.as_slice(),
+            (None, Part::One) => PART1_SLOPES,
+            (None, Part::Two) => PART2_SLOPES,
+        };
+        if let [slope] = slopes {
+            let trees = self.count_trees(*slope);
+            return Ok(Answer::new(trees, format!("Going {}, Santa encountered {} trees.", slope, trees)));
+        }
+        let mut lines = Vec::new();
+        let mut product = 1usize;
+        for slope in slopes {
+            let trees = self.count_trees(*slope);
+            lines.push(format!("Going {}, Santa encountered {} trees.", slope, trees));
+            product = product.checked_mul(trees)
+                .ok_or_else(|| Error::resource_limit("the product of the tree counts does not fit in 64 bits"))?;
+        }
+        lines.push(format!("The product of the tree counts is {}.", product));
+        Ok(Answer::new(product, lines.join("\n")))
+    }
+}
+
+impl Map {
+    fn count_trees(&self, slope: Slope) -> usize {
+        let right = slope.right % self.grid.width;
+        let mut column = 0;
+        let mut trees = 0;
+        for row in (0 .. self.grid.height).step_by(slope.down) {
+            if self.grid.get(column, row) == Some(TREE) {
+                trees += 1;
+            }
+            column = (column + right) % self.grid.width;
+        }
+        trees
+    }
+}
+
+#[derive(Clap)]
+#[clap(name = "day3", about = "Day 3: Toboggan Trajectory")]
+pub struct Solution {
+    #[clap(flatten)]
+    input: InputArgs,
+    #[clap(flatten)]
+    parts: PartArgs,
+    #[clap(
+        long = "slope",
+        number_of_values = 1,
+        conflicts_with = "part",
+        about = "A slope to check as right,down instead of the slopes for the part (may be repeated)"
+    )]
+    slopes: Vec<Slope>,
+}
+
+impl Solution {
+    pub fn invocation(self) -> Invocation {
+        if self.slopes.is_empty() {
+            Invocation::new(3, self.input.source(3), &self.parts.parts(&[Part::One, Part::Two]))
+        } else {
+            Invocation::new(3, self.input.source(3), &[Part::One])
+                .with_solver(TobogganTrajectory::with_slopes(self.slopes))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_lists_the_trees_for_each_slope() -> Result<()> {
+        let solver = TobogganTrajectory::with_slopes(vec![Slope::new(1, 1)?, Slope::new(1, 2)?]);
+        let answer = solver.run(EXAMPLES[0].input, Part::One)?;
+        let expected_description = "Going right 1, down 1, Santa encountered 2 trees.\n\
+                                    Going right 1, down 2, Santa encountered 2 trees.\n\
+                                    The product of the tree counts is 4.";
+        Ok(assert_eq!(answer.description, expected_description))
+    }
+
+    #[test]
+    fn the_map_repeats_to_the_right() -> Result<()> {
+        let answer = TobogganTrajectory::with_slopes(vec![Slope::new(4, 1)?]).run("#..\n.#.\n..#\n#..", Part::One)?;
+        Ok(assert_eq!(answer.value.to_string(), "4"))
+    }
+
+    #[test]
+    fn huge_steps_to_the_right_wrap_around_the_map() -> Result<()> {
+        let trees = |right| -> Result<String> {
+            let solver = TobogganTrajectory::with_slopes(vec![Slope::new(right, 1)?]);
+            Ok(solver.run(EXAMPLES[0].input, Part::One)?.value.to_string())
+        };
+        // The example map is 11 cells wide, and usize::MAX is 4 more than a multiple of 11.
+        Ok(assert_eq!(trees(usize::MAX)?, trees(4)?))
+    }
+
+    #[test]
+    fn it_reports_the_position_of_an_unexpected_cell() {
+        let expected_message = "parse error at line 2, column 3: expected '.' or '#'";
+        let result = TobogganTrajectory::new().parse("..#\n#.L\n");
+        assert_eq!(result.err().unwrap().to_string(), expected_message)
+    }
+}
@@ -0,0 +1,62 @@
+use crate::error::{Error, Result};
+use std::{fmt, str::FromStr};
+
+const NO_DESCENT: &str = "the slope has to go down at least one row";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Slope {
+    pub(super) right: usize,
+    pub(super) down: usize,
+}
+
+impl Slope {
+    pub fn new(right: usize, down: usize) -> Result<Slope> {
+        if down == 0 {
+            Err(Error::usage(NO_DESCENT))?
+        }
+        Ok(Slope { right, down })
+    }
+
+    pub fn right(&self) -> usize {
+        self.right
+    }
+
+    pub fn down(&self) -> usize {
+        self.down
+    }
+}
+
+impl FromStr for Slope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (right, down) = s.split_once(',').ok_or_else(|| format!("expected right,down but got {}", s))?;
+        let right = right.trim().parse().map_err(|err| format!("invalid distance right: {}", err))?;
+        let down = down.trim().parse().map_err(|err| format!("invalid distance down: {}", err))?;
+        Slope::new(right, down).map_err(|_| NO_DESCENT.to_string())
+    }
+}
+
+impl fmt::Display for Slope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&format!("right {}, down {}", self.right, self.down))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_right_then_down() {
+        assert_eq!("7,1".parse(), Ok(Slope { right: 7, down: 1 }));
+        assert_eq!("1, 2".parse(), Ok(Slope { right: 1, down: 2 }));
+    }
+
+    #[test]
+    fn it_rejects_slopes_that_never_go_down() {
+        assert_eq!("3,0".parse::<Slope>(), Err("the slope has to go down at least one row".to_string()));
+        assert!("3".parse::<Slope>().is_err());
+        assert_eq!(Slope::new(3, 0).unwrap_err().kind(), "usage");
+    }
+}
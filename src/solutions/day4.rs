@@ -0,0 +1,146 @@
+pub mod passport;
+pub mod record;
+
+use super::{
+    solver::{Answer, Example, Part, Puzzle, Solver},
+    Invocation, PartArgs,
+};
+use crate::{error::Result, input::InputArgs};
+use clap::{App, ArgMatches, Clap, FromArgMatches, IntoApp};
+use passport::{Invalidity, Passport};
+use record::Record;
+use std::fmt::Write;
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: Part::One,
+        input: include_str!("day4/example_part1.txt"),
+        expected: "2",
+    },
+    Example {
+        part: Part::Two,
+        input: include_str!("day4/example_part2.txt"),
+        expected: "4",
+    },
+];
+
+#[derive(Default)]
+pub struct PassportProcessing {
+    show_invalid: bool,
+}
+
+struct Batch {
+    records: Vec<Record>,
+    show_invalid: bool,
+}
+
+impl PassportProcessing {
+    pub fn new() -> PassportProcessing {
+        PassportProcessing::default()
+    }
+
+    pub fn showing_invalid() -> PassportProcessing {
+        PassportProcessing { show_invalid: true }
+    }
+}
+
+impl Solver for PassportProcessing {
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn name(&self) -> &'static str {
+        "Passport Processing"
+    }
+
+    fn command(&self) -> App<'static> {
+        Solution::into_app()
+    }
+
+    fn invocation(&self, matches: &ArgMatches) -> Invocation {
+        Solution::from_arg_matches(matches).invocation()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Puzzle>> {
+        Ok(Box::new(Batch {
+            records: Record::parse_all(input)?,
+            show_invalid: self.show_invalid,
+        }))
+    }
+}
+
+impl Puzzle for Batch {
+    fn solve(&self, part: Part) -> Result<Answer> {
+        let problems: Vec<(usize, Vec<Invalidity>)> = self.records.iter()
+            .map(|record| {
+                let problems = match part {
+                    Part::One => record.missing_fields().into_iter().map(Invalidity::Missing).collect(),
+                    Part::Two => Passport::validate(record).err().unwrap_or_default(),
+                };
+                (record.line, problems)
+            })
+            .filter(|(_, problems)| !problems.is_empty())
+            .collect();
+        let valid = self.records.len() - problems.len();
+        let mut description = match part {
+            Part::One => format!("{} of {} passports have every required field.", valid, self.records.len()),
+            Part::Two => format!("{} of {} passports are valid.", valid, self.records.len()),
+        };
+        if self.show_invalid {
+            for (line, problems) in problems {
+                let problems: Vec<String> = problems.iter().map(ToString::to_string).collect();
+                let _ = write!(description, "\nThe passport on line {} is invalid: {}", line, problems.join("; "));
+            }
+        }
+        Ok(Answer::new(valid, description))
+    }
+}
+
+#[derive(Clap)]
+#[clap(name = "day4", about = "Day 4: Passport Processing")]
+pub struct Solution {
+    #[clap(flatten)]
+    input: InputArgs,
+    #[clap(flatten)]
+    parts: PartArgs,
+    #[clap(long, about = "List the passports that are invalid and the fields that make them so")]
+    show_invalid: bool,
+}
+
+impl Solution {
+    pub fn invocation(self) -> Invocation {
+        let invocation = Invocation::new(4, self.input.source(4), &self.parts.parts(&[Part::One, Part::Two]));
+        if self.show_invalid {
+            invocation.with_solver(PassportProcessing::showing_invalid())
+        } else {
+            invocation
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_lists_the_fields_that_make_each_passport_invalid() -> Result<()> {
+        let batch = PassportProcessing::showing_invalid().parse(EXAMPLES[0].input)?;
+        let expected_description = "2 of 4 passports have every required field.\n\
+                                    The passport on line 4 is invalid: hgt is missing\n\
+                                    The passport on line 12 is invalid: byr is missing";
+        Ok(assert_eq!(batch.solve(Part::One)?.description, expected_description))
+    }
+
+    #[test]
+    fn part_two_also_lists_the_fields_with_invalid_values() -> Result<()> {
+        let batch = PassportProcessing::showing_invalid().parse("eyr:1972 cid:100\nhcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018")?;
+        let expected_description = "0 of 1 passports are valid.\n\
+                                    The passport on line 1 is invalid: byr is missing; eyr 1972 (must be between 2020 and 2030); \
+                                    hgt 170 (expected a number followed by cm or in); pid 186cm (expected a nine-digit number)";
+        Ok(assert_eq!(batch.solve(Part::Two)?.description, expected_description))
+    }
+}
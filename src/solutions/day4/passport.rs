@@ -0,0 +1,219 @@
+use super::record::{Field, Record};
+use std::{fmt, ops::RangeInclusive};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Height {
+    Centimeters(u16),
+    Inches(u16),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HairColor(pub u8, pub u8, pub u8);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EyeColor {
+    Amber,
+    Blue,
+    Brown,
+    Gray,
+    Green,
+    Hazel,
+    Other,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Passport {
+    pub birth_year: u16,
+    pub issue_year: u16,
+    pub expiration_year: u16,
+    pub height: Height,
+    pub hair_color: HairColor,
+    pub eye_color: EyeColor,
+    pub passport_id: String,
+    pub country_id: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Invalidity {
+    Missing(Field),
+    Invalid { field: Field, value: String, reason: String },
+}
+
+impl Passport {
+    pub fn validate(record: &Record) -> Result<Passport, Vec<Invalidity>> {
+        let mut problems = Vec::new();
+        let birth_year = check(record, Field::BirthYear, &mut problems, |value| year(value, 1920 ..= 2002));
+        let issue_year = check(record, Field::IssueYear, &mut problems, |value| year(value, 2010 ..= 2020));
+        let expiration_year = check(record, Field::ExpirationYear, &mut problems, |value| year(value, 2020 ..= 2030));
+        let height = check(record, Field::Height, &mut problems, height);
+        let hair_color = check(record, Field::HairColor, &mut problems, hair_color);
+        let eye_color = check(record, Field::EyeColor, &mut problems, eye_color);
+        let passport_id = check(record, Field::PassportId, &mut problems, passport_id);
+        match (birth_year, issue_year, expiration_year, height, hair_color, eye_color, passport_id) {
+            (
+                Some(birth_year),
+                Some(issue_year),
+                Some(expiration_year),
+                Some(height),
+                Some(hair_color),
+                Some(eye_color),
+                Some(passport_id),
+            ) => Ok(Passport {
+                birth_year,
+                issue_year,
+                expiration_year,
+                height,
+                hair_color,
+                eye_color,
+                passport_id,
+                country_id: record.fields.get(&Field::CountryId).cloned(),
+            }),
+            _ => Err(problems),
+        }
+    }
+}
+
+fn check<T>(
+    record: &Record,
+    field: Field,
+    problems: &mut Vec<Invalidity>,
+    validator: impl Fn(&str) -> Result<T, String>,
+) -> Option<T> {
+    match record.fields.get(&field) {
+        Some(value) => validator(value)
+            .map_err(|reason| problems.push(Invalidity::Invalid { field, value: value.clone(), reason }))
+            .ok(),
+        None => {
+            problems.push(Invalidity::Missing(field));
+            None
+        }
+    }
+}
+
+fn year(value: &str, range: RangeInclusive<u16>) -> Result<u16, String> {
+    if value.len() != 4 || !value.bytes().all(|b| b.is_ascii_digit()) {
+        Err("expected a four-digit year".to_string())?
+    }
+    let year = value.parse().map_err(|_| "expected a four-digit year".to_string())?;
+    if !range.contains(&year) {
+        Err(format!("must be between {} and {}", range.start(), range.end()))?
+    }
+    Ok(year)
+}
+
+fn height(value: &str) -> Result<Height, String> {
+    let digits = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (amount, unit) = value.split_at(digits);
+    let amount = amount.parse::<u16>().map_err(|_| "expected a number followed by cm or in".to_string());
+    match (amount?, unit) {
+        (amount, "cm") if (150 ..= 193).contains(&amount) => Ok(Height::Centimeters(amount)),
+        (_, "cm") => Err("must be between 150cm and 193cm".to_string()),
+        (amount, "in") if (59 ..= 76).contains(&amount) => Ok(Height::Inches(amount)),
+        (_, "in") => Err("must be between 59in and 76in".to_string()),
+        _ => Err("expected a number followed by cm or in".to_string()),
+    }
+}
+
+fn hair_color(value: &str) -> Result<HairColor, String> {
+    let hex = value.strip_prefix('#')
+        .filter(|hex| hex.len() == 6 && hex.bytes().all(|b| b.is_ascii_digit() || (b'a' ..= b'f').contains(&b)))
+        .ok_or_else(|| "expected # followed by six lowercase hex digits".to_string())?;
+    let octet = |index: usize| u8::from_str_radix(&hex[index .. index + 2], 16).unwrap_or_default();
+    Ok(HairColor(octet(0), octet(2), octet(4)))
+}
+
+fn eye_color(value: &str) -> Result<EyeColor, String> {
+    match value {
+        "amb" => Ok(EyeColor::Amber),
+        "blu" => Ok(EyeColor::Blue),
+        "brn" => Ok(EyeColor::Brown),
+        "gry" => Ok(EyeColor::Gray),
+        "grn" => Ok(EyeColor::Green),
+        "hzl" => Ok(EyeColor::Hazel),
+        "oth" => Ok(EyeColor::Other),
+        _ => Err("expected one of amb, blu, brn, gry, grn, hzl or oth".to_string()),
+    }
+}
+
+fn passport_id(value: &str) -> Result<String, String> {
+    if value.len() == 9 && value.bytes().all(|b| b.is_ascii_digit()) {
+        Ok(value.to_string())
+    } else {
+        Err("expected a nine-digit number".to_string())
+    }
+}
+
+impl fmt::Display for Invalidity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Invalidity::Missing(field) => write!(f, "{} is missing", field),
+            Invalidity::Invalid { field, value, reason } => write!(f, "{} {} ({})", field, value, reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(fields: &[(Field, &str)]) -> Record {
+        Record {
+            line: 1,
+            fields: fields.iter().map(|(field, value)| (*field, value.to_string())).collect(),
+        }
+    }
+
+    #[test]
+    fn it_builds_a_typed_passport_from_valid_fields() {
+        let record = record(&[
+            (Field::PassportId, "087499704"),
+            (Field::Height, "74in"),
+            (Field::EyeColor, "grn"),
+            (Field::IssueYear, "2012"),
+            (Field::ExpirationYear, "2030"),
+            (Field::BirthYear, "1980"),
+            (Field::HairColor, "#623a2f"),
+        ]);
+        let expected_passport = Passport {
+            birth_year: 1980,
+            issue_year: 2012,
+            expiration_year: 2030,
+            height: Height::Inches(74),
+            hair_color: HairColor(0x62, 0x3a, 0x2f),
+            eye_color: EyeColor::Green,
+            passport_id: "087499704".to_string(),
+            country_id: None,
+        };
+        assert_eq!(Passport::validate(&record), Ok(expected_passport))
+    }
+
+    #[test]
+    fn it_reports_every_field_that_makes_a_passport_invalid() {
+        let record = record(&[
+            (Field::Height, "59cm"),
+            (Field::EyeColor, "zzz"),
+            (Field::ExpirationYear, "2038"),
+            (Field::HairColor, "74454a"),
+            (Field::IssueYear, "2023"),
+            (Field::PassportId, "3556412378"),
+        ]);
+        let problems: Vec<String> = Passport::validate(&record).unwrap_err().iter().map(ToString::to_string).collect();
+        let expected_problems = vec![
+            "byr is missing",
+            "iyr 2023 (must be between 2010 and 2020)",
+            "eyr 2038 (must be between 2020 and 2030)",
+            "hgt 59cm (must be between 150cm and 193cm)",
+            "hcl 74454a (expected # followed by six lowercase hex digits)",
+            "ecl zzz (expected one of amb, blu, brn, gry, grn, hzl or oth)",
+            "pid 3556412378 (expected a nine-digit number)",
+        ];
+        assert_eq!(problems, expected_problems)
+    }
+
+    #[test]
+    fn heights_need_a_unit() {
+        assert_eq!(height("190in"), Err("must be between 59in and 76in".to_string()));
+        assert_eq!(height("190"), Err("expected a number followed by cm or in".to_string()));
+        assert_eq!(height("60in"), Ok(Height::Inches(60)));
+    }
+}
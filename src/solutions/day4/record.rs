@@ -0,0 +1,141 @@
+use crate::error::{Error, Result};
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while1},
+    character::complete::{char, multispace0, newline},
+    combinator::{eof, map},
+    multi::{many1, separated_list1},
+    sequence::{pair, separated_pair, terminated},
+    Finish, Offset,
+};
+use std::{collections::BTreeMap, fmt};
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Field {
+    BirthYear,
+    IssueYear,
+    ExpirationYear,
+    Height,
+    HairColor,
+    EyeColor,
+    PassportId,
+    CountryId,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub line: usize,
+    pub fields: BTreeMap<Field, String>,
+}
+
+impl Field {
+    pub const REQUIRED: &'static [Field] = &[
+        Field::BirthYear,
+        Field::IssueYear,
+        Field::ExpirationYear,
+        Field::Height,
+        Field::HairColor,
+        Field::EyeColor,
+        Field::PassportId,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Field::BirthYear => "byr",
+            Field::IssueYear => "iyr",
+            Field::ExpirationYear => "eyr",
+            Field::Height => "hgt",
+            Field::HairColor => "hcl",
+            Field::EyeColor => "ecl",
+            Field::PassportId => "pid",
+            Field::CountryId => "cid",
+        }
+    }
+
+    fn from_name(name: &str) -> Field {
+        match name {
+            "byr" => Field::BirthYear,
+            "iyr" => Field::IssueYear,
+            "eyr" => Field::ExpirationYear,
+            "hgt" => Field::Height,
+            "hcl" => Field::HairColor,
+            "ecl" => Field::EyeColor,
+            "pid" => Field::PassportId,
+            _ => Field::CountryId,
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl Record {
+    pub fn parse_all(input: &str) -> Result<Vec<Record>> {
+        fn entry<'a>() -> impl FnMut(&'a str) -> nom::IResult<&'a str, (&'a str, &'a str)> {
+            let field = alt((tag("byr"), tag("iyr"), tag("eyr"), tag("hgt"), tag("hcl"), tag("ecl"), tag("pid"), tag("cid")));
+            separated_pair(field, char(':'), take_while1(|c: char| !c.is_whitespace()))
+        }
+        let record = separated_list1(alt((char(' '), newline)), entry());
+        let records = separated_list1(pair(newline, many1(newline)), record);
+        let mut parser = terminated(
+            map(records, |records| {
+                records.into_iter()
+                    .map(|entries| Record {
+                        line: line_of(input, entries[0].0),
+                        fields: entries.into_iter()
+                            .map(|(name, value)| (Field::from_name(name), value.to_string()))
+                            .collect(),
+                    })
+                    .collect()
+            }),
+            pair(multispace0, eof),
+        );
+        let (_, records) = parser(input).finish().map_err(|e: nom::error::Error<&str>| {
+            let found = e.input.split_whitespace().next().unwrap_or_default();
+            Error::parse(line_of(input, e.input), format!("expected a passport field like byr:1937 but found {:?}", found))
+        })?;
+        Ok(records)
+    }
+
+    pub fn missing_fields(&self) -> Vec<Field> {
+        Field::REQUIRED.iter()
+            .filter(|field| !self.fields.contains_key(field))
+            .copied()
+            .collect()
+    }
+}
+
+fn line_of(input: &str, fragment: &str) -> usize {
+    input[.. input.offset(fragment)].matches('\n').count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_splits_records_on_blank_lines() -> Result<()> {
+        let records = Record::parse_all("ecl:gry pid:860033327\nbyr:1937\n\n\niyr:2013 cid:350\n")?;
+        let lines: Vec<usize> = records.iter().map(|record| record.line).collect();
+        assert_eq!(lines, vec![1, 5]);
+        assert_eq!(records[0].fields.get(&Field::BirthYear).map(String::as_str), Some("1937"));
+        Ok(assert_eq!(records[1].missing_fields(), vec![
+            Field::BirthYear,
+            Field::ExpirationYear,
+            Field::Height,
+            Field::HairColor,
+            Field::EyeColor,
+            Field::PassportId,
+        ]))
+    }
+
+    #[test]
+    fn it_reports_where_an_unknown_field_is() {
+        let result = Record::parse_all("ecl:gry pid:860033327\n\nbyr:1937 zip:90210\n");
+        let expected_message = "parse error at line 3: expected a passport field like byr:1937 but found \"zip:90210\"";
+        assert_eq!(result.unwrap_err().to_string(), expected_message)
+    }
+}
@@ -0,0 +1,154 @@
+pub mod boarding_pass;
+
+use super::{
+    solver::{Answer, Example, Part, Puzzle, Solver},
+    Invocation, PartArgs,
+};
+use crate::{
+    error::{Error, Result},
+    input::InputArgs,
+};
+use boarding_pass::BoardingPass;
+use clap::{App, ArgMatches, Clap, FromArgMatches, IntoApp};
+
+const EXAMPLES: &[Example] = &[Example {
+    part: Part::One,
+    input: include_str!("day5/example.txt"),
+    expected: "820",
+}];
+
+#[derive(Default)]
+pub struct BinaryBoarding {
+    encode: bool,
+}
+
+struct Passes {
+    passes: Vec<BoardingPass>,
+    encode: bool,
+}
+
+impl BinaryBoarding {
+    pub fn new() -> BinaryBoarding {
+        BinaryBoarding::default()
+    }
+
+    pub fn encoding() -> BinaryBoarding {
+        BinaryBoarding { encode: true }
+    }
+}
+
+impl Solver for BinaryBoarding {
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn name(&self) -> &'static str {
+        "Binary Boarding"
+    }
+
+    fn command(&self) -> App<'static> {
+        Solution::into_app()
+    }
+
+    fn invocation(&self, matches: &ArgMatches) -> Invocation {
+        Solution::from_arg_matches(matches).invocation()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Puzzle>> {
+        let passes = input.lines()
+            .enumerate()
+            .map(|(index, line)| {
+                let pass = if self.encode {
+                    line.parse()
+                        .map_err(anyhow::Error::from)
+                        .and_then(BoardingPass::from_id)
+                } else {
+                    BoardingPass::parse(line)
+                };
+                pass.map_err(|err| Error::parse(index + 1, err))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Box::new(Passes {
+            passes,
+            encode: self.encode,
+        }))
+    }
+}
+
+impl Puzzle for Passes {
+    fn solve(&self, part: Part) -> Result<Answer> {
+        if self.encode {
+            let codes: Vec<String> = self.passes.iter().map(BoardingPass::code).collect();
+            let seats: Vec<String> = self.passes.iter().map(ToString::to_string).collect();
+            return Ok(Answer::new(codes.join(","), seats.join("\n")));
+        }
+        match part {
+            Part::One => {
+                let highest = self.passes.iter()
+                    .max()
+                    .ok_or_else(|| Error::unsolvable("there are no boarding passes"))?;
+                Ok(Answer::new(highest.id(), format!("The highest seat is {}.", highest)))
+            }
+            Part::Two => {
+                let mut passes = self.passes.clone();
+                passes.sort();
+                let id = passes.windows(2)
+                    .find(|pair| pair[1].id() == pair[0].id() + 2)
+                    .map(|pair| pair[0].id() + 1)
+                    .ok_or_else(|| Error::unsolvable("no empty seat has occupied seats on both sides"))?;
+                let seat = BoardingPass::from_id(id).map_err(Error::unsolvable)?;
+                Ok(Answer::new(seat.id(), format!("Your seat is {}.", seat)))
+            }
+        }
+    }
+}
+
+#[derive(Clap)]
+#[clap(name = "day5", about = "Day 5: Binary Boarding")]
+pub struct Solution {
+    #[clap(flatten)]
+    input: InputArgs,
+    #[clap(flatten)]
+    parts: PartArgs,
+    #[clap(long, conflicts_with = "part", about = "Read seat IDs from the input and print their seat codes instead")]
+    encode: bool,
+}
+
+impl Solution {
+    pub fn invocation(self) -> Invocation {
+        if self.encode {
+            Invocation::new(5, self.input.source(5), &[Part::One]).with_solver(BinaryBoarding::encoding())
+        } else {
+            Invocation::new(5, self.input.source(5), &self.parts.parts(&[Part::One, Part::Two]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_finds_the_seat_between_two_occupied_ones() -> Result<()> {
+        let answer = BinaryBoarding::new().run("FFFBBBFRRR\nFFFBBBFRLR\nFFFBBBFRLL\nFFFFFFFLLL\n", Part::Two)?;
+        Ok(assert_eq!(answer.description, "Your seat is FFFBBBFRRL (row 14, column 6, seat ID 118)."))
+    }
+
+    #[test]
+    fn it_encodes_seat_ids_into_codes() -> Result<()> {
+        let answer = BinaryBoarding::encoding().run("357\n820\n", Part::One)?;
+        assert_eq!(answer.value.to_string(), "FBFBBFFRLR,BBFFBBFRLL");
+        Ok(assert_eq!(answer.description, "FBFBBFFRLR (row 44, column 5, seat ID 357)\nBBFFBBFRLL (row 102, column 4, seat ID 820)"))
+    }
+
+    #[test]
+    fn it_reports_the_line_of_an_invalid_code() {
+        let expected_message = "parse error at line 2: invalid seat code length (got 9 instead of 10)";
+        let result = BinaryBoarding::new().parse("FBFBBFFRLR\nFBFBBFFRL\n");
+        assert_eq!(result.err().unwrap().to_string(), expected_message)
+    }
+}
@@ -0,0 +1,117 @@
+use crate::solutions::day14::comporter::{Bit, Mask, MASK_LEN};
+use anyhow::{anyhow, Result};
+use bitvec::prelude::*;
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt,
+};
+
+pub const CODE_LEN: usize = 10;
+pub const MAX_SEAT_ID: usize = (1 << CODE_LEN) - 1;
+
+const ROW_LEN: usize = 7;
+const ROW_BITS: (u8, u8) = (b'F', b'B');
+const COLUMN_BITS: (u8, u8) = (b'L', b'R');
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct BoardingPass {
+    id: usize,
+}
+
+impl BoardingPass {
+    pub fn parse(code: &str) -> Result<BoardingPass> {
+        if code.len() != CODE_LEN {
+            Err(anyhow!("invalid seat code length (got {} instead of {})", code.len(), CODE_LEN))?
+        }
+        let mut bits = vec![Bit::Zero; MASK_LEN - CODE_LEN];
+        for (offset, letter) in code.bytes().enumerate() {
+            let (zero, one) = if offset < ROW_LEN { ROW_BITS } else { COLUMN_BITS };
+            match letter {
+                _ if letter == zero => bits.push(Bit::Zero),
+                _ if letter == one => bits.push(Bit::One),
+                _ => Err(anyhow!(
+                    "expected {} or {} at offset {}",
+                    zero as char,
+                    one as char,
+                    offset
+                ))?,
+            }
+        }
+        let id: usize = Mask::try_from(bits)?.try_into()?;
+        Ok(BoardingPass { id })
+    }
+
+    pub fn from_id(id: usize) -> Result<BoardingPass> {
+        if id > MAX_SEAT_ID {
+            Err(anyhow!("seat IDs go up to {} (got {})", MAX_SEAT_ID, id))?
+        }
+        Ok(BoardingPass { id })
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn row(&self) -> usize {
+        self.id >> (CODE_LEN - ROW_LEN)
+    }
+
+    pub fn column(&self) -> usize {
+        self.id & ((1 << (CODE_LEN - ROW_LEN)) - 1)
+    }
+
+    pub fn code(&self) -> String {
+        let id = self.id as u64;
+        let bits: Vec<Bit> = id.view_bits::<Msb0>()
+            .iter()
+            .skip(u64::BITS as usize - MASK_LEN)
+            .map(|bit| if *bit { Bit::One } else { Bit::Zero })
+            .collect();
+        let mask = Mask::try_from(bits).expect("valid mask");
+        mask.iter()
+            .skip(MASK_LEN - CODE_LEN)
+            .enumerate()
+            .map(|(offset, bit)| {
+                let (zero, one) = if offset < ROW_LEN { ROW_BITS } else { COLUMN_BITS };
+                if *bit == Bit::One { one as char } else { zero as char }
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for BoardingPass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (row {}, column {}, seat ID {})", self.code(), self.row(), self.column(), self.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_the_row_column_and_seat_id() -> Result<()> {
+        let pass = BoardingPass::parse("FBFBBFFRLR")?;
+        Ok(assert_eq!((pass.row(), pass.column(), pass.id()), (44, 5, 357)))
+    }
+
+    #[test]
+    fn encodes_a_seat_id_back_into_a_code() -> Result<()> {
+        for code in &["BFFFBBFRRR", "FFFBBBFRRR", "BBFFBBFRLL", "FFFFFFFLLL", "BBBBBBBRRR"] {
+            assert_eq!(BoardingPass::from_id(BoardingPass::parse(code)?.id())?.code(), *code);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_letters_in_the_wrong_half() {
+        let result = BoardingPass::parse("FBFBBFRRLR").unwrap_err();
+        assert_eq!(result.to_string(), "expected F or B at offset 6")
+    }
+
+    #[test]
+    fn rejects_seat_ids_that_do_not_fit_in_a_code() {
+        let result = BoardingPass::from_id(1024).unwrap_err();
+        assert_eq!(result.to_string(), "seat IDs go up to 1023 (got 1024)")
+    }
+}
@@ -0,0 +1,185 @@
+pub mod answer_set;
+
+use super::{
+    solver::{Answer, Example, Part, Puzzle, Solver},
+    Invocation, PartArgs,
+};
+use crate::{
+    error::{Error, Result},
+    input::InputArgs,
+};
+use answer_set::AnswerSet;
+use clap::{App, ArgMatches, Clap, FromArgMatches, IntoApp};
+use std::fmt::Write;
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: Part::One,
+        input: include_str!("day6/example.txt"),
+        expected: "11",
+    },
+    Example {
+        part: Part::Two,
+        input: include_str!("day6/example.txt"),
+        expected: "6",
+    },
+];
+
+#[derive(Default)]
+pub struct CustomCustoms {
+    show_groups: bool,
+}
+
+pub struct Group {
+    pub line: usize,
+    pub people: Vec<AnswerSet>,
+}
+
+struct Declarations {
+    groups: Vec<Group>,
+    show_groups: bool,
+}
+
+impl CustomCustoms {
+    pub fn new() -> CustomCustoms {
+        CustomCustoms::default()
+    }
+
+    pub fn showing_groups() -> CustomCustoms {
+        CustomCustoms { show_groups: true }
+    }
+}
+
+impl Group {
+    pub fn anyone(&self) -> AnswerSet {
+        self.people.iter().fold(AnswerSet::EMPTY, |answers, person| answers | *person)
+    }
+
+    pub fn everyone(&self) -> AnswerSet {
+        self.people.iter().fold(AnswerSet::ALL, |answers, person| answers & *person)
+    }
+}
+
+impl Solver for CustomCustoms {
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn name(&self) -> &'static str {
+        "Custom Customs"
+    }
+
+    fn command(&self) -> App<'static> {
+        Solution::into_app()
+    }
+
+    fn invocation(&self, matches: &ArgMatches) -> Invocation {
+        Solution::from_arg_matches(matches).invocation()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Puzzle>> {
+        let mut groups: Vec<Group> = Vec::new();
+        let mut in_group = false;
+        for (index, line) in input.lines().enumerate() {
+            if line.is_empty() {
+                in_group = false;
+                continue;
+            }
+            let person = AnswerSet::parse(line).map_err(|err| Error::parse(index + 1, err))?;
+            match groups.last_mut() {
+                Some(group) if in_group => group.people.push(person),
+                _ => groups.push(Group {
+                    line: index + 1,
+                    people: vec![person],
+                }),
+            }
+            in_group = true;
+        }
+        if groups.is_empty() {
+            Err(Error::parse(1, "expected at least one group of answers"))?
+        }
+        Ok(Box::new(Declarations {
+            groups,
+            show_groups: self.show_groups,
+        }))
+    }
+}
+
+impl Puzzle for Declarations {
+    fn solve(&self, part: Part) -> Result<Answer> {
+        let (who, answers): (&str, Vec<AnswerSet>) = match part {
+            Part::One => ("anyone", self.groups.iter().map(Group::anyone).collect()),
+            Part::Two => ("everyone", self.groups.iter().map(Group::everyone).collect()),
+        };
+        let total: usize = answers.iter().map(AnswerSet::len).sum();
+        let mut description = format!(
+            "Summed over {} groups, {} answered yes to {} questions.",
+            self.groups.len(),
+            who,
+            total
+        );
+        if self.show_groups {
+            for (group, answers) in self.groups.iter().zip(answers) {
+                let _ = write!(
+                    description,
+                    "\nThe group of {} on line {}: {} answered yes to {} of 26 questions",
+                    group.people.len(),
+                    group.line,
+                    who,
+                    answers.len()
+                );
+                if !answers.is_empty() {
+                    let _ = write!(description, " ({})", answers);
+                }
+            }
+        }
+        Ok(Answer::new(total, description))
+    }
+}
+
+#[derive(Clap)]
+#[clap(name = "day6", about = "Day 6: Custom Customs")]
+pub struct Solution {
+    #[clap(flatten)]
+    input: InputArgs,
+    #[clap(flatten)]
+    parts: PartArgs,
+    #[clap(long, about = "List the questions each group answered yes to")]
+    show_groups: bool,
+}
+
+impl Solution {
+    pub fn invocation(self) -> Invocation {
+        let invocation = Invocation::new(6, self.input.source(6), &self.parts.parts(&[Part::One, Part::Two]));
+        if self.show_groups {
+            invocation.with_solver(CustomCustoms::showing_groups())
+        } else {
+            invocation
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_breaks_the_answers_down_by_group() -> Result<()> {
+        let declarations = CustomCustoms::showing_groups().parse("ab\nac\n\n\nb\n")?;
+        let expected_description = "Summed over 2 groups, everyone answered yes to 2 questions.\n\
+                                    The group of 2 on line 1: everyone answered yes to 1 of 26 questions (a)\n\
+                                    The group of 1 on line 5: everyone answered yes to 1 of 26 questions (b)";
+        Ok(assert_eq!(declarations.solve(Part::Two)?.description, expected_description))
+    }
+
+    #[test]
+    fn it_reports_the_line_of_an_invalid_answer() {
+        let expected_message = "parse error at line 3: expected a question from a to z at offset 1";
+        let result = CustomCustoms::new().parse("abc\n\na1\n");
+        assert_eq!(result.err().unwrap().to_string(), expected_message)
+    }
+}
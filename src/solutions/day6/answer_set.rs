@@ -0,0 +1,84 @@
+use anyhow::{anyhow, Result};
+use std::{
+    fmt,
+    ops::{BitAnd, BitOr},
+};
+
+const QUESTIONS: u32 = 26;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AnswerSet(u32);
+
+impl AnswerSet {
+    pub const EMPTY: AnswerSet = AnswerSet(0);
+    pub const ALL: AnswerSet = AnswerSet((1 << QUESTIONS) - 1);
+
+    pub fn parse(line: &str) -> Result<AnswerSet> {
+        let mut set = AnswerSet::EMPTY;
+        for (offset, question) in line.bytes().enumerate() {
+            match question {
+                b'a' ..= b'z' => set.0 |= 1 << (question - b'a'),
+                _ => Err(anyhow!("expected a question from a to z at offset {}", offset))?,
+            }
+        }
+        Ok(set)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, question: char) -> bool {
+        question.is_ascii_lowercase() && self.0 & (1 << (question as u8 - b'a')) != 0
+    }
+}
+
+impl BitOr for AnswerSet {
+    type Output = AnswerSet;
+
+    fn bitor(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 | other.0)
+    }
+}
+
+impl BitAnd for AnswerSet {
+    type Output = AnswerSet;
+
+    fn bitand(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 & other.0)
+    }
+}
+
+impl fmt::Display for AnswerSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let questions: String = ('a' ..= 'z').filter(|question| self.contains(*question)).collect();
+        f.pad(&questions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_and_intersection_combine_answers() -> Result<()> {
+        let (ab, ac) = (AnswerSet::parse("ab")?, AnswerSet::parse("ca")?);
+        assert_eq!((ab | ac).to_string(), "abc");
+        Ok(assert_eq!((ab & ac).to_string(), "a"))
+    }
+
+    #[test]
+    fn repeated_answers_count_once() -> Result<()> {
+        Ok(assert_eq!(AnswerSet::parse("zzaz")?.len(), 2))
+    }
+
+    #[test]
+    fn rejects_answers_outside_the_alphabet() {
+        let result = AnswerSet::parse("abC").unwrap_err();
+        assert_eq!(result.to_string(), "expected a question from a to z at offset 2")
+    }
+}
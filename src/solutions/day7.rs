@@ -0,0 +1,205 @@
+pub mod rule;
+
+use super::{
+    graph::WeightedGraph,
+    solver::{Answer, Example, Part, Puzzle, Solver},
+    Invocation, PartArgs,
+};
+use crate::{
+    error::{Error, Result},
+    input::InputArgs,
+};
+use clap::{App, ArgMatches, Clap, FromArgMatches, IntoApp};
+use rule::Rule;
+use std::collections::HashMap;
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: Part::One,
+        input: include_str!("day7/example.txt"),
+        expected: "4",
+    },
+    Example {
+        part: Part::Two,
+        input: include_str!("day7/example.txt"),
+        expected: "32",
+    },
+    Example {
+        part: Part::Two,
+        input: include_str!("day7/example_part2.txt"),
+        expected: "126",
+    },
+];
+
+const DEFAULT_BAG: &str = "shiny gold";
+
+pub struct HandyHaversacks {
+    bag: String,
+}
+
+struct Rules {
+    graph: WeightedGraph<String>,
+    bag: String,
+}
+
+impl HandyHaversacks {
+    pub fn new() -> HandyHaversacks {
+        HandyHaversacks::for_bag(DEFAULT_BAG)
+    }
+
+    pub fn for_bag(bag: &str) -> HandyHaversacks {
+        HandyHaversacks { bag: bag.to_string() }
+    }
+}
+
+impl Default for HandyHaversacks {
+    fn default() -> Self {
+        HandyHaversacks::new()
+    }
+}
+
+impl Solver for HandyHaversacks {
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn name(&self) -> &'static str {
+        "Handy Haversacks"
+    }
+
+    fn command(&self) -> App<'static> {
+        Solution::into_app()
+    }
+
+    fn invocation(&self, matches: &ArgMatches) -> Invocation {
+        Solution::from_arg_matches(matches).invocation()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Puzzle>> {
+        let mut graph = WeightedGraph::new();
+        let mut rule_lines = HashMap::new();
+        for (index, line) in input.lines().enumerate() {
+            let rule = Rule::parse(line).map_err(|err| Error::parse(index + 1, err))?;
+            if let Some(previous) = rule_lines.insert(rule.container.clone(), index + 1) {
+                Err(Error::parse(
+                    index + 1,
+                    format!("{} bags already have a rule on line {}", rule.container, previous),
+                ))?
+            }
+            graph.add_node(rule.container.clone());
+            for (quantity, bag) in rule.contents {
+                graph.add_edge(rule.container.clone(), bag, quantity);
+            }
+        }
+        Ok(Box::new(Rules {
+            graph,
+            bag: self.bag.clone(),
+        }))
+    }
+}
+
+impl Puzzle for Rules {
+    fn solve(&self, part: Part) -> Result<Answer> {
+        let id = self.graph
+            .id(&self.bag)
+            .ok_or_else(|| Error::unsolvable(format!("no rule mentions {} bags", self.bag)))?;
+        match part {
+            Part::One => {
+                let containers = self.graph.ancestors(id).len();
+                Ok(Answer::new(
+                    containers,
+                    format!("{} bag colours can eventually contain a {} bag.", containers, self.bag),
+                ))
+            }
+            Part::Two => {
+                if let Some(cycle) = self.graph.find_cycle_from(id) {
+                    let bags: Vec<&str> = cycle.into_iter().map(|id| self.graph.node(id).as_str()).collect();
+                    Err(Error::unsolvable(format!("the bags contain themselves: {}", bags.join(" → "))))?
+                }
+                let contained = self.graph
+                    .weighted_descendants(id, &mut HashMap::new())
+                    .ok_or_else(|| Error::resource_limit("the number of bags inside overflows 64 bits"))?;
+                Ok(Answer::new(
+                    contained,
+                    format!("A {} bag must contain {} other bags.", self.bag, contained),
+                ))
+            }
+        }
+    }
+}
+
+#[derive(Clap)]
+#[clap(name = "day7", about = "Day 7: Handy Haversacks")]
+pub struct Solution {
+    #[clap(flatten)]
+    input: InputArgs,
+    #[clap(flatten)]
+    parts: PartArgs,
+    #[clap(long, default_value = DEFAULT_BAG, about = "The colour of the bag to look for")]
+    bag: String,
+}
+
+impl Solution {
+    pub fn invocation(self) -> Invocation {
+        let invocation = Invocation::new(7, self.input.source(7), &self.parts.parts(&[Part::One, Part::Two]))
+            .with_solver(HandyHaversacks::for_bag(&self.bag));
+        if self.bag == DEFAULT_BAG {
+            invocation
+        } else {
+            invocation.unchecked()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_answers_for_any_bag_colour() -> Result<()> {
+        let rules = HandyHaversacks::for_bag("faded blue").parse(EXAMPLES[0].input)?;
+        assert_eq!(rules.solve(Part::One)?.description, "7 bag colours can eventually contain a faded blue bag.");
+        Ok(assert_eq!(rules.solve(Part::Two)?.description, "A faded blue bag must contain 0 other bags."))
+    }
+
+    #[test]
+    fn it_refuses_to_count_bags_that_contain_themselves() -> Result<()> {
+        let rules = HandyHaversacks::new().parse(
+            "shiny gold bags contain 1 dark red bag.\n\
+             dark red bags contain 2 light blue bags.\n\
+             light blue bags contain 1 dark red bag.\n",
+        )?;
+        assert_eq!(rules.solve(Part::One)?.value.to_string(), "0");
+        let expected_message = "the input is unsolvable: the bags contain themselves: dark red → light blue → dark red";
+        Ok(assert_eq!(rules.solve(Part::Two).err().unwrap().to_string(), expected_message))
+    }
+
+    #[test]
+    fn it_reports_an_unknown_bag_colour() -> Result<()> {
+        let rules = HandyHaversacks::for_bag("plaid purple").parse(EXAMPLES[0].input)?;
+        let expected_message = "the input is unsolvable: no rule mentions plaid purple bags";
+        Ok(assert_eq!(rules.solve(Part::One).err().unwrap().to_string(), expected_message))
+    }
+
+    #[test]
+    fn it_rejects_a_second_rule_for_the_same_bag() {
+        let expected_message = "parse error at line 2: faded blue bags already have a rule on line 1";
+        let result = HandyHaversacks::new().parse(
+            "faded blue bags contain no other bags.\nfaded blue bags contain 1 shiny gold bag.\n",
+        );
+        assert_eq!(result.err().unwrap().to_string(), expected_message)
+    }
+
+    #[test]
+    fn it_reports_an_overflowing_count_as_a_resource_limit() -> Result<()> {
+        let rules = HandyHaversacks::new().parse(
+            "shiny gold bags contain 4294967296 dark red bags.\n\
+             dark red bags contain 4294967296 light blue bags.\n",
+        )?;
+        Ok(assert_eq!(rules.solve(Part::Two).err().unwrap().kind(), "resource_limit"))
+    }
+}
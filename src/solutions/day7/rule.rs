@@ -0,0 +1,65 @@
+use anyhow::{anyhow, Result};
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_until},
+    character::complete::{char, digit1},
+    combinator::{eof, map, map_res},
+    multi::separated_list1,
+    sequence::{pair, separated_pair, terminated},
+    Finish,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rule {
+    pub container: String,
+    pub contents: Vec<(u64, String)>,
+}
+
+impl Rule {
+    pub fn parse(line: &str) -> Result<Rule> {
+        fn contents<'a>() -> impl FnMut(&'a str) -> nom::IResult<&'a str, Vec<(u64, &'a str)>> {
+            let quantity = map_res(digit1, |s: &str| s.parse::<u64>());
+            let bag = terminated(
+                separated_pair(quantity, char(' '), take_until(" bag")),
+                alt((tag(" bags"), tag(" bag"))),
+            );
+            alt((map(tag("no other bags"), |_| Vec::new()), separated_list1(tag(", "), bag)))
+        }
+        let mut parser = terminated(
+            pair(terminated(take_until(" bags contain "), tag(" bags contain ")), contents()),
+            pair(char('.'), eof),
+        );
+        let (_, (container, contents)) = parser(line).finish().map_err(|e| anyhow!("{}", e))?;
+        Ok(Rule {
+            container: container.to_string(),
+            contents: contents.into_iter()
+                .map(|(quantity, bag)| (quantity, bag.to_string()))
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_bags_a_bag_contains() -> Result<()> {
+        let expected_rule = Rule {
+            container: "light red".to_string(),
+            contents: vec![(1, "bright white".to_string()), (2, "muted yellow".to_string())],
+        };
+        Ok(assert_eq!(Rule::parse("light red bags contain 1 bright white bag, 2 muted yellow bags.")?, expected_rule))
+    }
+
+    #[test]
+    fn parses_bags_that_contain_nothing() -> Result<()> {
+        let rule = Rule::parse("faded blue bags contain no other bags.")?;
+        Ok(assert_eq!(rule.contents, vec![]))
+    }
+
+    #[test]
+    fn requires_the_closing_period() {
+        assert!(Rule::parse("bright white bags contain 1 shiny gold bag").is_err())
+    }
+}
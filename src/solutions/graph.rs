@@ -0,0 +1,190 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    hash::Hash,
+};
+
+#[derive(Clone, Debug)]
+pub struct WeightedGraph<N> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    edges: Vec<Vec<(usize, u64)>>,
+    reverse_edges: Vec<Vec<usize>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Visit {
+    InProgress,
+    Done,
+}
+
+impl<N: Clone + Eq + Hash> WeightedGraph<N> {
+    pub fn new() -> WeightedGraph<N> {
+        WeightedGraph {
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            edges: Vec::new(),
+            reverse_edges: Vec::new(),
+        }
+    }
+
+    pub fn add_node(&mut self, node: N) -> usize {
+        if let Some(id) = self.index.get(&node) {
+            return *id;
+        }
+        let id = self.nodes.len();
+        self.index.insert(node.clone(), id);
+        self.nodes.push(node);
+        self.edges.push(Vec::new());
+        self.reverse_edges.push(Vec::new());
+        id
+    }
+
+    pub fn add_edge(&mut self, from: N, to: N, weight: u64) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        self.edges[from].push((to, weight));
+        self.reverse_edges[to].push(from);
+    }
+
+    pub fn id(&self, node: &N) -> Option<usize> {
+        self.index.get(node).copied()
+    }
+
+    pub fn node(&self, id: usize) -> &N {
+        &self.nodes[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn edges(&self, id: usize) -> &[(usize, u64)] {
+        &self.edges[id]
+    }
+
+    pub fn ancestors(&self, id: usize) -> BTreeSet<usize> {
+        let mut seen = BTreeSet::new();
+        let mut pending = vec![id];
+        while let Some(id) = pending.pop() {
+            for parent in &self.reverse_edges[id] {
+                if seen.insert(*parent) {
+                    pending.push(*parent);
+                }
+            }
+        }
+        seen
+    }
+
+    pub fn find_cycle(&self) -> Option<Vec<usize>> {
+        self.search_for_cycle(0 .. self.len())
+    }
+
+    pub fn find_cycle_from(&self, id: usize) -> Option<Vec<usize>> {
+        self.search_for_cycle(id ..= id)
+    }
+
+    fn search_for_cycle(&self, mut roots: impl Iterator<Item = usize>) -> Option<Vec<usize>> {
+        fn visit<N>(
+            graph: &WeightedGraph<N>,
+            id: usize,
+            visits: &mut [Option<Visit>],
+            path: &mut Vec<usize>,
+        ) -> Option<Vec<usize>> {
+            visits[id] = Some(Visit::InProgress);
+            path.push(id);
+            for (child, _) in &graph.edges[id] {
+                match visits[*child] {
+                    Some(Visit::InProgress) => {
+                        let start = path.iter().position(|id| id == child).unwrap_or_default();
+                        let mut cycle = path[start ..].to_vec();
+                        cycle.push(*child);
+                        return Some(cycle);
+                    }
+                    Some(Visit::Done) => (),
+                    None => {
+                        if let Some(cycle) = visit(graph, *child, visits, path) {
+                            return Some(cycle);
+                        }
+                    }
+                }
+            }
+            path.pop();
+            visits[id] = Some(Visit::Done);
+            None
+        }
+        let mut visits = vec![None; self.len()];
+        roots.find_map(|id| {
+            if visits[id].is_none() {
+                visit(self, id, &mut visits, &mut Vec::new())
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn weighted_descendants(&self, id: usize, memo: &mut HashMap<usize, u64>) -> Option<u64> {
+        if let Some(count) = memo.get(&id) {
+            return Some(*count);
+        }
+        let mut count = 0u64;
+        for (child, weight) in &self.edges[id] {
+            let below = self.weighted_descendants(*child, memo)?;
+            count = count.checked_add(weight.checked_mul(below.checked_add(1)?)?)?;
+        }
+        memo.insert(id, count);
+        Some(count)
+    }
+}
+
+impl<N: Clone + Eq + Hash> Default for WeightedGraph<N> {
+    fn default() -> Self {
+        WeightedGraph::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&'static str, &'static str, u64)]) -> WeightedGraph<&'static str> {
+        let mut graph = WeightedGraph::new();
+        for (from, to, weight) in edges {
+            graph.add_edge(*from, *to, *weight);
+        }
+        graph
+    }
+
+    #[test]
+    fn ancestors_include_every_node_that_reaches_the_node() {
+        let graph = graph(&[("a", "b", 1), ("b", "c", 3), ("d", "c", 2), ("c", "e", 1)]);
+        let ancestors: Vec<&str> = graph.ancestors(graph.id(&"c").unwrap()).into_iter().map(|id| *graph.node(id)).collect();
+        assert_eq!(ancestors, vec!["a", "b", "d"])
+    }
+
+    #[test]
+    fn weighted_descendants_multiply_along_each_path() {
+        let graph = graph(&[("a", "b", 2), ("b", "c", 3), ("a", "c", 1)]);
+        let mut memo = HashMap::new();
+        assert_eq!(graph.weighted_descendants(graph.id(&"a").unwrap(), &mut memo), Some(2 * (1 + 3) + 1));
+        assert_eq!(memo.get(&graph.id(&"b").unwrap()), Some(&3))
+    }
+
+    #[test]
+    fn it_finds_a_cycle() {
+        let graph = graph(&[("a", "b", 1), ("b", "c", 1), ("c", "b", 1)]);
+        let cycle: Vec<&str> = graph.find_cycle().unwrap().into_iter().map(|id| *graph.node(id)).collect();
+        assert_eq!(cycle, vec!["b", "c", "b"]);
+        assert_eq!(graph.find_cycle_from(graph.id(&"c").unwrap()).map(|cycle| cycle.len()), Some(3));
+        assert_eq!(self::graph(&[("a", "b", 1), ("a", "c", 1), ("b", "c", 1)]).find_cycle(), None)
+    }
+
+    #[test]
+    fn cycles_out_of_reach_are_ignored_from_a_node() {
+        let graph = graph(&[("a", "b", 1), ("c", "d", 1), ("d", "c", 1)]);
+        assert_eq!(graph.find_cycle_from(graph.id(&"a").unwrap()), None);
+        assert!(graph.find_cycle().is_some())
+    }
+}
@@ -0,0 +1,73 @@
+use crate::error::{Error, Result};
+use std::{fmt, str};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<u8>,
+}
+
+impl Grid {
+    pub fn parse(grid: impl AsRef<str>) -> Result<Grid> {
+        let grid = grid.as_ref();
+        let width = grid.lines().next().map_or(0, str::len);
+        if width == 0 {
+            Err(Error::parse(1, "the grid is empty"))?
+        }
+        if let Some(index) = grid.lines().position(|row| row.len() != width) {
+            Err(Error::parse(index + 1, format!("expected a row of {} cells", width)))?
+        }
+        Ok(Grid {
+            width,
+            height: grid.lines().count(),
+            cells: grid.lines().flat_map(str::bytes).collect(),
+        })
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+        if x < self.width && y < self.height {
+            Some(self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn cells(&self) -> &[u8] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [u8] {
+        &mut self.cells
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let chunks: std::result::Result<Vec<&str>, str::Utf8Error> = self.cells.chunks(self.width)
+            .map(str::from_utf8)
+            .collect();
+        let chunks = chunks.or(Err(fmt::Error))?;
+        let formatted_str = chunks.join("\n");
+        write!(f, "{}", formatted_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reports_the_first_row_with_a_different_width() {
+        let expected_message = "parse error at line 3: expected a row of 3 cells";
+        let result = Grid::parse("..#\n#..\n.#\n...");
+        assert_eq!(result.err().unwrap().to_string(), expected_message)
+    }
+
+    #[test]
+    fn cells_are_addressed_by_column_and_row() -> Result<()> {
+        let grid = Grid::parse("..#\n#..")?;
+        assert_eq!((grid.width, grid.height), (3, 2));
+        Ok(assert_eq!((grid.get(2, 0), grid.get(0, 1), grid.get(3, 0)), (Some(b'#'), Some(b'#'), None)))
+    }
+}
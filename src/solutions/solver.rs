@@ -0,0 +1,220 @@
+use super::Invocation;
+use crate::{error::Result, progress::Progress};
+use clap::{App, ArgMatches};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Integer(i128),
+    Text(String),
+}
+
+#[derive(Debug)]
+pub struct Answer {
+    pub value: Value,
+    pub description: String,
+}
+
+pub struct Example {
+    pub part: Part,
+    pub input: &'static str,
+    pub expected: &'static str,
+}
+
+pub trait Puzzle {
+    fn solve(&self, part: Part) -> Result<Answer>;
+
+    fn solve_with(&self, part: Part, _progress: &Progress) -> Result<Answer> {
+        self.solve(part)
+    }
+}
+
+pub trait Solver {
+    fn day(&self) -> u8;
+    fn name(&self) -> &'static str;
+    fn parse(&self, input: &str) -> Result<Box<dyn Puzzle>>;
+    fn command(&self) -> App<'static>;
+    fn invocation(&self, matches: &ArgMatches) -> Invocation;
+
+    fn parts(&self) -> &'static [Part] {
+        &[Part::One, Part::Two]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    fn run(&self, input: &str, part: Part) -> Result<Answer> {
+        self.parse(input)?.solve(part)
+    }
+}
+
+#[derive(Default)]
+pub struct Registry {
+    solvers: Vec<Box<dyn Solver>>,
+}
+
+impl Part {
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.number().to_string())
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Integer(value) => f.pad(&value.to_string()),
+            Value::Text(value) => f.pad(value),
+        }
+    }
+}
+
+macro_rules! integer_values {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(value: $t) -> Self {
+                    Value::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+integer_values!(i32, i64, u64, usize);
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Text(value)
+    }
+}
+
+impl Answer {
+    pub fn new(value: impl Into<Value>, description: impl Into<String>) -> Answer {
+        Answer {
+            value: value.into(),
+            description: description.into(),
+        }
+    }
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    pub fn register(&mut self, solver: impl Solver + 'static) -> &mut Self {
+        self.solvers.push(Box::new(solver));
+        self.solvers.sort_by_key(|solver| solver.day());
+        self
+    }
+
+    pub fn get(&self, day: u8) -> Option<&dyn Solver> {
+        self.iter().find(|solver| solver.day() == day)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Solver> {
+        self.solvers.iter().map(|solver| solver.as_ref())
+    }
+
+    pub fn commands(&self) -> impl Iterator<Item = App<'static>> + '_ {
+        self.iter().map(|solver| solver.command())
+    }
+
+    pub fn invocation(&self, command: &str, matches: &ArgMatches) -> Option<Invocation> {
+        self.iter()
+            .find(|solver| solver.command().get_name() == command)
+            .map(|solver| solver.invocation(matches))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Error, input::Source};
+
+    struct Echo(u8);
+
+    impl Puzzle for String {
+        fn solve(&self, part: Part) -> Result<Answer> {
+            Ok(Answer::new(format!("{}{}", self, part), "echo"))
+        }
+    }
+
+    impl Solver for Echo {
+        fn day(&self) -> u8 {
+            self.0
+        }
+
+        fn name(&self) -> &'static str {
+            "Echo"
+        }
+
+        fn parse(&self, input: &str) -> Result<Box<dyn Puzzle>> {
+            if input.is_empty() {
+                Err(Error::parse(1, "no input"))
+            } else {
+                Ok(Box::new(input.to_string()))
+            }
+        }
+
+        fn command(&self) -> App<'static> {
+            App::new(format!("echo{}", self.0))
+        }
+
+        fn invocation(&self, _matches: &ArgMatches) -> Invocation {
+            Invocation::new(self.0, Source::Stdin, &[Part::One])
+        }
+    }
+
+    #[test]
+    fn it_looks_up_solvers_by_day() {
+        let mut registry = Registry::new();
+        registry.register(Echo(3)).register(Echo(1));
+        let solver = registry.get(3).expect("day 3 is registered");
+        assert_eq!(solver.day(), 3);
+        assert!(registry.get(2).is_none());
+    }
+
+    #[test]
+    fn it_lists_solvers_in_day_order() {
+        let mut registry = Registry::new();
+        registry.register(Echo(3)).register(Echo(1)).register(Echo(2));
+        let days: Vec<u8> = registry.iter().map(|solver| solver.day()).collect();
+        assert_eq!(days, [1, 2, 3]);
+    }
+
+    #[test]
+    fn it_builds_invocations_from_the_matching_command() {
+        let mut registry = Registry::new();
+        registry.register(Echo(1)).register(Echo(2));
+        let app = App::new("test").subcommands(registry.commands());
+        let matches = app.get_matches_from(["test", "echo2"]);
+        let invocation = matches.subcommand()
+            .and_then(|(command, matches)| registry.invocation(command, matches));
+        assert_eq!(invocation.map(|invocation| invocation.day), Some(2));
+        assert!(registry.invocation("echo3", &ArgMatches::default()).is_none());
+    }
+
+    #[test]
+    fn it_runs_a_part_through_the_parser() -> Result<()> {
+        let answer = Echo(1).run("abc", Part::Two)?;
+        assert_eq!(answer.value, Value::Text("abc2".to_string()));
+        Ok(())
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{
+    error::{Error, Result},
+    progress::Progress,
+    solutions::{solver::Value, Report},
+};
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+type Snapshot = BTreeMap<PathBuf, (SystemTime, u64)>;
+
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    snapshot: Snapshot,
+    interval: Duration,
+}
+
+pub enum Event<'a> {
+    Watching(&'a [PathBuf]),
+    Changed(&'a [PathBuf]),
+    Changes(&'a [Change]),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Change {
+    pub day: u8,
+    pub part: u8,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>, interval: Duration) -> Result<Watcher> {
+        let snapshot = snapshot(&paths)?;
+        Ok(Watcher {
+            paths,
+            snapshot,
+            interval,
+        })
+    }
+
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    pub fn wait(&mut self, progress: &Progress) -> Result<Vec<PathBuf>> {
+        loop {
+            thread::sleep(self.interval);
+            if progress.is_cancelled() {
+                Err(Error::cancelled("stopped watching for changes"))?
+            }
+            let snapshot = snapshot(&self.paths)?;
+            let mut changed: Vec<PathBuf> = snapshot.iter()
+                .filter(|(path, stamp)| self.snapshot.get(*path) != Some(stamp))
+                .map(|(path, _)| path.clone())
+                .collect();
+            changed.extend(self.snapshot.keys().filter(|path| !snapshot.contains_key(*path)).cloned());
+            self.snapshot = snapshot;
+            if !changed.is_empty() {
+                changed.sort();
+                return Ok(changed);
+            }
+        }
+    }
+}
+
+pub fn run(
+    paths: Vec<PathBuf>,
+    progress: &Progress,
+    mut run: impl FnMut() -> Vec<Report>,
+    mut on_event: impl FnMut(Event),
+) -> Result<Infallible> {
+    let mut watcher = Watcher::new(paths, Duration::from_millis(500))?;
+    let mut previous = run();
+    loop {
+        on_event(Event::Watching(watcher.paths()));
+        if progress.is_cancelled() {
+            Err(Error::cancelled("interrupted"))?
+        }
+        let changed = watcher.wait(progress)?;
+        on_event(Event::Changed(&changed));
+        let reports = run();
+        if !reports.is_empty() {
+            on_event(Event::Changes(&diff(&previous, &reports)));
+            previous = reports;
+        }
+    }
+}
+
+fn snapshot(paths: &[PathBuf]) -> Result<Snapshot> {
+    let mut snapshot = Snapshot::new();
+    for path in paths {
+        if path.is_dir() {
+            for entry in std::fs::read_dir(path)? {
+                let path = entry?.path();
+                if path.is_file() {
+                    stamp(&path, &mut snapshot);
+                }
+            }
+        } else {
+            stamp(path, &mut snapshot);
+        }
+    }
+    Ok(snapshot)
+}
+
+fn stamp(path: &Path, snapshot: &mut Snapshot) {
+    if let Ok(metadata) = std::fs::metadata(path) {
+        let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        snapshot.insert(path.to_path_buf(), (modified, metadata.len()));
+    }
+}
+
+pub fn diff(old: &[Report], new: &[Report]) -> Vec<Change> {
+    let answers = |reports: &[Report]| -> BTreeMap<(u8, u8), Value> {
+        reports.iter()
+            .map(|report| ((report.day, report.part.number()), report.answer.value.clone()))
+            .collect()
+    };
+    let mut old = answers(old);
+    let mut changes = Vec::new();
+    for ((day, part), new) in answers(new) {
+        match old.remove(&(day, part)) {
+            Some(old) if old == new => (),
+            old => changes.push(Change {
+                day,
+                part,
+                old,
+                new: Some(new),
+            }),
+        }
+    }
+    changes.extend(old.into_iter().map(|((day, part), old)| Change {
+        day,
+        part,
+        old: Some(old),
+        new: None,
+    }));
+    changes.sort_by_key(|change| (change.day, change.part));
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solutions::{report, solver::Part};
+
+    #[test]
+    fn it_reports_only_the_answers_that_changed() {
+        let old = [report(11, Part::One, 37), report(11, Part::Two, 26), report(12, Part::One, 25)];
+        let new = [report(11, Part::One, 37), report(11, Part::Two, 27), report(13, Part::One, 295)];
+        let expected_changes = vec![
+            Change { day: 11, part: 2, old: Some(Value::Integer(26)), new: Some(Value::Integer(27)) },
+            Change { day: 12, part: 1, old: Some(Value::Integer(25)), new: None },
+            Change { day: 13, part: 1, old: None, new: Some(Value::Integer(295)) },
+        ];
+        assert_eq!(diff(&old, &new), expected_changes)
+    }
+
+    #[test]
+    fn it_notices_when_a_watched_file_changes() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("advent_2020-watch-{}.txt", std::process::id()));
+        std::fs::write(&path, "939\n")?;
+        let mut watcher = Watcher::new(vec![path.clone()], Duration::from_millis(10))?;
+        std::fs::write(&path, "939\n7,13,x,x,59\n")?;
+        let changed = watcher.wait(&Progress::new());
+        std::fs::remove_file(&path)?;
+        Ok(assert_eq!(changed?, vec![path]))
+    }
+
+    #[test]
+    fn it_runs_again_when_a_watched_file_changes_until_cancelled() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("advent_2020-watch-run-{}.txt", std::process::id()));
+        std::fs::write(&path, "37")?;
+        let progress = Progress::new();
+        let read = || -> Vec<Report> {
+            let value: u64 = std::fs::read_to_string(&path).unwrap().parse().unwrap();
+            vec![report(11, Part::One, value)]
+        };
+        let mut changes = Vec::new();
+        let mut watched = 0;
+        let result = run(vec![path.clone()], &progress, read, |event| match event {
+            Event::Watching(_) if watched == 0 => {
+                watched += 1;
+                std::fs::write(&path, "2600").unwrap();
+            }
+            Event::Watching(_) => progress.cancel(),
+            Event::Changed(changed) => assert_eq!(changed, std::slice::from_ref(&path)),
+            Event::Changes(found) => changes.push((found[0].old.clone(), found[0].new.clone())),
+        });
+        std::fs::remove_file(&path)?;
+        assert_eq!(changes, [(Some(Value::Integer(37)), Some(Value::Integer(2600)))]);
+        Ok(assert_eq!(result.err().map(|err| err.kind()), Some("cancelled")))
+    }
+
+    #[test]
+    fn it_notices_edits_made_during_the_first_run() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("advent_2020-watch-first-{}.txt", std::process::id()));
+        std::fs::write(&path, "37")?;
+        let progress = Progress::new();
+        let mut runs = 0;
+        let read = || -> Vec<Report> {
+            let value: u64 = std::fs::read_to_string(&path).unwrap().parse().unwrap();
+            if runs == 0 {
+                std::fs::write(&path, "2600").unwrap();
+            }
+            runs += 1;
+            vec![report(11, Part::One, value)]
+        };
+        let mut changes = Vec::new();
+        let result = run(vec![path.clone()], &progress, read, |event| {
+            if let Event::Changes(found) = event {
+                changes.push(found[0].new.clone());
+                progress.cancel();
+            }
+        });
+        std::fs::remove_file(&path)?;
+        assert_eq!(changes, [Some(Value::Integer(2600))]);
+        Ok(assert_eq!(result.err().map(|err| err.kind()), Some("cancelled")))
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+};
+
+struct Case {
+    name: &'static str,
+    args: &'static [&'static str],
+    stdin: Option<&'static str>,
+    exit_code: i32,
+}
+
+const CASES: &[Case] = &[
+    Case { name: "list", args: &["list"], stdin: None, exit_code: 0 },
+    Case { name: "list-json", args: &["--format", "json", "list"], stdin: None, exit_code: 0 },
+    Case { name: "day1", args: &["day1", "fixtures/day1.txt", "--part", "both"], stdin: None, exit_code: 0 },
+    Case { name: "day1-json", args: &["--format", "json", "day1", "fixtures/day1.txt", "--part", "both"], stdin: None, exit_code: 0 },
+    Case { name: "day1-entries", args: &["day1", "fixtures/day1.txt", "-k", "4", "--target", "2796"], stdin: None, exit_code: 0 },
+    Case { name: "day2", args: &["day2", "fixtures/day2.txt", "--show-invalid"], stdin: None, exit_code: 0 },
+    Case { name: "day2-json", args: &["--format", "json", "day2", "fixtures/day2.txt", "--policy", "position"], stdin: None, exit_code: 0 },
+    Case { name: "day3", args: &["day3", "fixtures/day3.txt"], stdin: None, exit_code: 0 },
+    Case { name: "day3-slopes", args: &["day3", "fixtures/day3.txt", "--slope", "1,1", "--slope", "7,1"], stdin: None, exit_code: 0 },
+    Case { name: "day3-huge-slope", args: &["day3", "fixtures/day3.txt", "--slope", "18446744073709551615,1"], stdin: None, exit_code: 0 },
+    Case { name: "day4", args: &["day4", "fixtures/day4.txt", "--part", "2", "--show-invalid"], stdin: None, exit_code: 0 },
+    Case { name: "day4-json", args: &["--format", "json", "day4", "fixtures/day4.txt"], stdin: None, exit_code: 0 },
+    Case { name: "day5", args: &["day5", "fixtures/day5.txt"], stdin: None, exit_code: 0 },
+    Case { name: "day5-encode", args: &["day5", "--encode", "--input-text", "357"], stdin: None, exit_code: 0 },
+    Case { name: "day6", args: &["day6", "fixtures/day6.txt", "--show-groups"], stdin: None, exit_code: 0 },
+    Case { name: "day6-json", args: &["--format", "json", "day6", "fixtures/day6.txt"], stdin: None, exit_code: 0 },
+    Case { name: "day7", args: &["day7", "fixtures/day7.txt"], stdin: None, exit_code: 0 },
+    Case { name: "day7-bag", args: &["day7", "fixtures/day7.txt", "--bag", "faded blue"], stdin: None, exit_code: 0 },
+    Case { name: "day7-example", args: &["day7", "--example"], stdin: None, exit_code: 0 },
+    Case { name: "day7-example-bag", args: &["day7", "--example", "--part", "1", "--bag", "faded blue"], stdin: None, exit_code: 0 },
+    Case { name: "day11", args: &["day11", "fixtures/day11.txt", "--part", "both"], stdin: None, exit_code: 0 },
+    Case { name: "day11-json", args: &["--format", "json", "day11", "fixtures/day11.txt", "--part", "both"], stdin: None, exit_code: 0 },
+    Case { name: "day12", args: &["day12", "fixtures/day12.txt", "--part", "both"], stdin: None, exit_code: 0 },
+    Case { name: "day12-json", args: &["--format", "json", "day12", "fixtures/day12.txt", "--part", "both"], stdin: None, exit_code: 0 },
+    Case { name: "day13", args: &["day13", "fixtures/day13.txt"], stdin: None, exit_code: 0 },
+    Case { name: "day13-json", args: &["--format", "json", "day13", "fixtures/day13.txt"], stdin: None, exit_code: 0 },
+    Case { name: "day14", args: &["day14", "fixtures/day14.txt", "--part", "both"], stdin: None, exit_code: 0 },
+    Case { name: "day14-json", args: &["--format", "json", "day14", "fixtures/day14.txt", "--part", "both"], stdin: None, exit_code: 0 },
+    Case { name: "day15", args: &["day15", "fixtures/day15.txt"], stdin: None, exit_code: 0 },
+    Case { name: "day15-json", args: &["--format", "json", "day15", "fixtures/day15.txt"], stdin: None, exit_code: 0 },
+    Case { name: "day15-nth", args: &["day15", "fixtures/day15.txt", "--nth", "10"], stdin: None, exit_code: 0 },
+    Case { name: "day15-nth-text", args: &["day15", "--input-text", "0,3,6", "--nth", "10"], stdin: None, exit_code: 0 },
+    Case { name: "day15-nth-example", args: &["day15", "--example", "--nth", "10"], stdin: None, exit_code: 0 },
+    Case { name: "day12-example", args: &["day12", "--example", "--part", "both"], stdin: None, exit_code: 0 },
+    Case { name: "day14-invalid", args: &["day14", "fixtures/day14-invalid.txt"], stdin: None, exit_code: 4 },
+    Case { name: "day14-invalid-json", args: &["--format", "json", "day14", "fixtures/day14-invalid.txt"], stdin: None, exit_code: 4 },
+    Case { name: "missing-input", args: &["day11", "fixtures/day99.txt"], stdin: None, exit_code: 3 },
+    Case { name: "watch-misuse", args: &["--watch", "list"], stdin: None, exit_code: 2 },
+    Case { name: "watch-misuse-json", args: &["--format", "json", "--watch", "list"], stdin: None, exit_code: 2 },
+    Case { name: "stdin", args: &["day13", "-"], stdin: Some("fixtures/day13.txt"), exit_code: 0 },
+    Case { name: "all-json", args: &["--format", "json", "all", "--inputs", "fixtures"], stdin: None, exit_code: 0 },
+    Case { name: "all-missing-inputs", args: &["all", "--inputs", "fixtures/missing"], stdin: None, exit_code: 3 },
+    Case { name: "all-missing-inputs-json", args: &["--format", "json", "all", "--inputs", "fixtures/missing"], stdin: None, exit_code: 3 },
+    Case { name: "generate", args: &["generate", "13", "--size", "5", "--seed", "1"], stdin: None, exit_code: 0 },
+    Case { name: "generate-too-many-buses", args: &["generate", "13", "--size", "7"], stdin: None, exit_code: 2 },
+    Case { name: "completions", args: &["completions", "bash"], stdin: None, exit_code: 0 },
+];
+
+fn tests_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests")
+}
+
+fn run(case: &Case) -> Output {
+    let stdin = match case.stdin {
+        Some(path) => Stdio::from(File::open(tests_dir().join(path)).expect("the stdin fixture exists")),
+        None => Stdio::null(),
+    };
+    Command::new(env!("CARGO_BIN_EXE_advent_2020"))
+        .args(case.args)
+        .current_dir(tests_dir())
+        .stdin(stdin)
+        .output()
+        .expect("the binary can be run")
+}
+
+fn normalize(output: &[u8]) -> String {
+    String::from_utf8_lossy(output)
+        .lines()
+        .map(|line| match serde_json::from_str::<serde_json::Value>(line) {
+            Ok(mut record) if record.get("elapsed_ms").is_some() => {
+                record["elapsed_ms"] = 0.into();
+                record.to_string()
+            }
+            _ => line.to_string(),
+        })
+        .map(|line| line + "\n")
+        .collect()
+}
+
+fn check(path: &Path, actual: &str, bless: bool, failures: &mut Vec<String>) {
+    if bless {
+        if actual.is_empty() {
+            let _ = std::fs::remove_file(path);
+        } else {
+            std::fs::write(path, actual).expect("the golden file can be written");
+        }
+        return;
+    }
+    let expected = std::fs::read_to_string(path).unwrap_or_default();
+    if expected != actual {
+        failures.push(format!(
+            "{} does not match:\n--- expected\n{}--- actual\n{}",
+            path.display(),
+            expected,
+            actual
+        ));
+    }
+}
+
+#[test]
+fn the_cli_output_matches_the_golden_files() {
+    let bless = std::env::var_os("BLESS").is_some_and(|value| value == "1");
+    let golden = tests_dir().join("golden");
+    let mut failures = Vec::new();
+    for case in CASES {
+        let output = run(case);
+        if output.status.code() != Some(case.exit_code) {
+            failures.push(format!(
+                "{} exited with {:?} instead of {}",
+                case.name,
+                output.status.code(),
+                case.exit_code
+            ));
+        }
+        check(&golden.join(format!("{}.stdout", case.name)), &normalize(&output.stdout), bless, &mut failures);
+        check(&golden.join(format!("{}.stderr", case.name)), &normalize(&output.stderr), bless, &mut failures);
+    }
+    assert!(
+        failures.is_empty(),
+        "{}\n\nRun `BLESS=1 cargo test --test cli` to accept the new output.",
+        failures.join("\n")
+    );
+}